@@ -1,48 +1,45 @@
-// Example: How to add a new NetworkedHealth component
-
-// Step 1: Define your component (in your actual component file)
-use crate::networked_component;
-
-#[derive(Component, Debug, Clone, Copy)]
-pub struct Health {
-    pub current: f32,
-    pub max: f32,
+// Example: How Health is networked, and how to network a new component the same way
+//
+// Step 1: Define your component (the real one lives in src/ecs/plugins/health/components.rs)
+//
+//     #[derive(Component, Debug, Clone, Copy)]
+//     pub struct Health {
+//         pub current: f32,
+//         pub max: f32,
+//         pub regen_per_sec: f32,
+//     }
+//
+// Step 2: Pick a short key in src/ecs/plugins/network/components.rs
+//
+//     pub const HEALTH_KEY: &str = "hp";
+//
+// Step 3: Add a change detection system in src/ecs/plugins/network/systems.rs that
+// writes the compact value into the entity's NetworkSnapshot and marks the key dirty
+//
+//     pub fn detect_health_changes_system(
+//         mut query: Query<(&mut NetworkDirty, &mut NetworkSnapshot, &Health),
+//                         (With<NetworkId>, Changed<Health>)>,
+//     ) { ... }
+//
+// Step 4: Register it in NetworkPlugin before proximity_detection_system
+//
+// That's it! The snapshot feeds full syncs for clients that see the entity for the
+// first time, and dirty keys become delta updates for clients already in view.
+
+use std::collections::HashMap;
+
+const HEALTH_KEY: &str = "hp";
+
+fn round_to_2dp(value: f32) -> f32 {
+    (value * 100.0).round() / 100.0
 }
 
-// Step 2: Create networked version using the macro
-networked_component! {
-    pub struct NetworkedHealth {
-        #[threshold = 0.1]  // Only sync if change > 0.1 HP
-        pub current: f32,
-        #[threshold = 0.1]
-        pub max: f32,
-    }
-}
-
-// Step 3: Implement conversion
-impl_from_source!(NetworkedHealth, Health, {current, max});
-
-// Step 4: Register in component_registry.rs - just add ONE line:
-/*
-register_networked_components!(
-    NetworkedPosition,
-    NetworkedVelocity,
-    NetworkedHealth     // <- Add this line
-);
-*/
-
-// Step 5: Add auto-sync in your plugin
-/*
-auto_sync_networked!(app, NetworkedHealth, Health);
-*/
+fn main() {
+    let (current, max) = (72.5_f32, 100.0_f32);
 
-// That's it! Now NetworkedHealth is automatically synchronized with:
-// - Automatic change detection (only syncs when health changes by >0.1)
-// - Delta updates (only changed fields are sent)
-// - Full sync for new clients
-// - Message compression
-// - Zero performance overhead when health doesn't change
+    // This is what the health component of an entity update looks like on the wire
+    let mut components = HashMap::new();
+    components.insert(HEALTH_KEY, serde_json::json!([round_to_2dp(current), round_to_2dp(max)]));
 
-fn main() {
-    println!("This is just an example file showing how easy it is to add networked components!");
-}
\ No newline at end of file
+    println!("{}", serde_json::json!({ "i": 7, "c": components }));
+}
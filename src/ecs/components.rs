@@ -1,6 +1,15 @@
 use bevy::prelude::*;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::ecs::plugins::pathfinding::components::PathFollower;
+use crate::ecs::plugins::ability::components::AbilityCooldowns;
+use crate::ecs::plugins::health::components::Health;
+use crate::ecs::plugins::chat::components::{ChatChannel, ChatRateLimit};
+use crate::ecs::plugins::party::components::PartyCommand;
+use crate::ecs::plugins::inventory::components::InventoryCommand;
+use crate::ecs::plugins::zone::components::{ZoneId, MAIN_ZONE};
+use crate::ecs::plugins::collision::components::{Collider, COLLISION_LAYER_ALL, COLLISION_LAYER_CHARACTER, COLLISION_LAYER_PLAYER};
 
 // ============================================================================
 // INPUT COMPONENTS
@@ -10,8 +19,25 @@ use std::collections::HashMap;
 pub enum InputCommand {
     Move { direction: Vec2 },
     Stop,
+    // Cast an ability at another entity, identified by its network ID
+    CastAtTarget { ability: String, target: u32 },
+    // Cast an ability toward a direction, aimed at the ability's full range
+    CastInDirection { ability: String, direction: Vec2 },
+    // Latest server tick ("tk") the client has received, used for lag compensation
+    Ack { tick: u64 },
+    Chat { channel: ChatChannel, text: String },
+    Party(PartyCommand),
+    Inventory(InventoryCommand),
 }
 
+// Input as received from a client, before anti-cheat validation
+#[derive(Event)]
+pub struct ClientInputEvent {
+    pub player_id: u32,
+    pub command: InputCommand,
+}
+
+// Validated input, consumed by the simulation
 #[derive(Event)]
 pub struct InputCommandEvent {
     pub player_id: u32,
@@ -71,19 +97,49 @@ pub struct Player {
     pub id: u32,
 }
 
+// Persistent account identity, present when the client logged in with one
+#[derive(Component, Debug, Clone)]
+pub struct Account {
+    pub id: String,
+}
+
+// Clients see a little past their view radius, so every view check covers this
+// multiple of it
+pub const VIEW_RANGE_SCALE: f32 = 1.4;
+
+// Entities come into view within radius and only leave past exit_radius, so one
+// hovering at the edge doesn't flicker in and out
 #[derive(Component, Debug, Clone, Copy)]
 pub struct ViewDistance {
     pub radius: f32,
+    pub exit_radius: f32,
+}
+
+impl ViewDistance {
+    // An exit radius inside the enter radius would drop entities the moment they appear
+    pub fn new(radius: f32, exit_radius: f32) -> Self {
+        Self { radius, exit_radius: exit_radius.max(radius) }
+    }
+
+    // Distance within which entities come into view
+    pub fn enter_range(&self) -> f32 {
+        self.radius * VIEW_RANGE_SCALE
+    }
+
+    // Distance past which entities in view leave it
+    pub fn exit_range(&self) -> f32 {
+        self.exit_radius * VIEW_RANGE_SCALE
+    }
 }
 
 impl Default for ViewDistance {
     fn default() -> Self {
-        Self { radius: 300.0 } // Default view radius
+        Self::new(300.0, 330.0) // Default view radius
     }
 }
 
 
-#[derive(Component, Debug, Clone, Copy)]
+#[derive(Component, Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct CharacterProfile {
     pub max_speed: f32,
     pub acceleration: f32,
@@ -100,6 +156,13 @@ impl Default for CharacterProfile {
     }
 }
 
+const PLAYER_COLLIDER_RADIUS: f32 = 16.0;
+const CHARACTER_COLLIDER_RADIUS: f32 = 16.0;
+const PLAYER_MAX_HEALTH: f32 = 100.0;
+const PLAYER_HEALTH_REGEN: f32 = 2.0;
+const CHARACTER_MAX_HEALTH: f32 = 100.0;
+const CHARACTER_HEALTH_REGEN: f32 = 1.0;
+
 #[derive(Bundle)]
 pub struct PlayerBundle {
     pub player: Player,
@@ -109,13 +172,16 @@ pub struct PlayerBundle {
     pub character_profile: CharacterProfile,
     pub friction: Friction,
     pub view_distance: ViewDistance,
+    pub collider: Collider,
+    pub health: Health,
+    pub ability_cooldowns: AbilityCooldowns,
+    pub chat_rate_limit: ChatRateLimit,
+    pub zone: ZoneId,
 }
 
 impl PlayerBundle {
-    pub fn new(player_id: u32, game_config: &GameConfig) -> Self {
+    pub fn new(player_id: u32, game_config: &GameConfig, rng: &mut impl Rng) -> Self {
         let profile = CharacterProfile::default();
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
         let x = rng.gen_range(0.0..game_config.world_bounds.x);
         let y = rng.gen_range(0.0..game_config.world_bounds.y);
         
@@ -127,6 +193,11 @@ impl PlayerBundle {
             character_profile: profile,
             friction: Friction::default(),
             view_distance: ViewDistance::default(),
+            collider: Collider::solid(PLAYER_COLLIDER_RADIUS, COLLISION_LAYER_PLAYER, COLLISION_LAYER_ALL),
+            health: Health::new(PLAYER_MAX_HEALTH, PLAYER_HEALTH_REGEN),
+            ability_cooldowns: AbilityCooldowns::default(),
+            chat_rate_limit: ChatRateLimit::default(),
+            zone: MAIN_ZONE,
         }
     }
 }
@@ -136,6 +207,12 @@ pub struct Character {
     pub id: u32,
 }
 
+// Name of the AI archetype driving an NPC character
+#[derive(Component, Debug, Clone)]
+pub struct NpcArchetype {
+    pub name: String,
+}
+
 #[derive(Bundle)]
 pub struct CharacterBundle {
     pub character: Character,
@@ -144,14 +221,16 @@ pub struct CharacterBundle {
     pub desired_velocity: DesiredVelocity,
     pub character_profile: CharacterProfile,
     pub friction: Friction,
+    pub collider: Collider,
+    pub path_follower: PathFollower,
+    pub health: Health,
+    pub zone: ZoneId,
 }
 
 impl CharacterBundle {
-    pub fn new(character_id: u32, position: Option<Position>, game_config: &GameConfig) -> Self {
+    pub fn new(character_id: u32, position: Option<Position>, game_config: &GameConfig, rng: &mut impl Rng) -> Self {
         let profile = CharacterProfile::default();
         let pos = position.unwrap_or_else(|| {
-            use rand::Rng;
-            let mut rng = rand::thread_rng();
             Position {
                 x: rng.gen_range(0.0..game_config.world_bounds.x),
                 y: rng.gen_range(0.0..game_config.world_bounds.y),
@@ -165,6 +244,10 @@ impl CharacterBundle {
             desired_velocity: DesiredVelocity::default(),
             character_profile: profile,
             friction: Friction::default(),
+            collider: Collider::solid(CHARACTER_COLLIDER_RADIUS, COLLISION_LAYER_CHARACTER, COLLISION_LAYER_ALL),
+            path_follower: PathFollower::default(),
+            health: Health::new(CHARACTER_MAX_HEALTH, CHARACTER_HEALTH_REGEN),
+            zone: MAIN_ZONE,
         }
     }
 }
@@ -177,6 +260,7 @@ impl CharacterBundle {
 #[derive(Event)]
 pub struct PlayerSpawnEvent {
     pub player_id: u32,
+    pub account: Option<String>,
 }
 
 #[derive(Event)]
@@ -184,10 +268,35 @@ pub struct PlayerDespawnEvent {
     pub player_id: u32,
 }
 
+// Asks the transport to disconnect a player; despawn follows through PlayerDespawnEvent
+#[derive(Event)]
+pub struct PlayerKickEvent {
+    pub player_id: u32,
+    pub reason: String,
+}
+
+// Asks the transport to send a player to another server, which expects them with `token`
+#[derive(Event)]
+pub struct PlayerRedirectEvent {
+    pub player_id: u32,
+    pub url: String,
+    pub token: String,
+}
+
+// A player who connected with a handoff token issued by another server
+#[derive(Event)]
+pub struct PlayerHandoffArrivalEvent {
+    pub player_id: u32,
+    pub token: String,
+}
+
 #[derive(Event)]
 pub struct CharacterSpawnEvent {
     pub character_id: u32,
     pub position: Option<Position>,
+    pub archetype: Option<String>,
+    // Networked characters get a NetworkId and are synced to nearby clients
+    pub networked: bool,
 }
 
 #[derive(Event)]
@@ -232,3 +341,16 @@ impl PlayerRegistry {
     }
 }
 
+// Receives OS shutdown signals (Ctrl+C / SIGTERM) from a background thread
+#[derive(Resource)]
+pub struct ShutdownSignal {
+    pub sender: crossbeam_channel::Sender<()>,
+    pub receiver: crossbeam_channel::Receiver<()>,
+}
+
+impl Default for ShutdownSignal {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::bounded(1);
+        Self { sender, receiver }
+    }
+}
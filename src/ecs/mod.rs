@@ -2,5 +2,5 @@ pub mod components;
 pub mod systems;
 pub mod plugins;
 
-pub use plugins::{WebSocketPlugin, NetworkPlugin};
+pub use plugins::{WebSocketPlugin, NetworkPlugin, SimulationPlugin, ReplayPlugin, SnapshotPlugin, PersistencePlugin, CollisionPlugin, WorldMapPlugin, PathfindingPlugin, AiPlugin, HealthPlugin, AbilityPlugin, ProjectilePlugin, LagCompensationPlugin, AntiCheatPlugin, ChatPlugin, PartyPlugin, InventoryPlugin, LootPlugin, ZonePlugin, ShardPlugin};
 
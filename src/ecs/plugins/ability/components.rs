@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use crate::ecs::plugins::projectile::components::ProjectileSpec;
+
+// ============================================================================
+// ABILITY DATA
+// ============================================================================
+
+// Area an ability affects once it resolves
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AbilityShape {
+    // Only the targeted entity
+    Single,
+    // Everything within `radius` of the aim point
+    Circle { radius: f32 },
+    // Everything within range of the caster and inside the cone toward the aim point
+    Cone { angle_degrees: f32 },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AbilityDefinition {
+    pub range: f32,
+    pub cooldown_secs: f32,
+    #[serde(default)]
+    pub cast_time_secs: f32,
+    pub damage: f32,
+    pub shape: AbilityShape,
+    // Launches a projectile toward the aim point instead of hitting instantly
+    #[serde(default)]
+    pub projectile: Option<ProjectileSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AbilityDataFile {
+    pub abilities: HashMap<String, AbilityDefinition>,
+}
+
+// ============================================================================
+// ABILITY COMPONENTS
+// ============================================================================
+
+// Tick at which each ability can next be cast
+#[derive(Component, Debug, Clone, Default)]
+pub struct AbilityCooldowns {
+    pub ready_tick: HashMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum AbilityAim {
+    Target(Entity),
+    Direction(Vec2),
+}
+
+// A validated cast waiting for its cast time to finish
+#[derive(Component, Debug, Clone)]
+pub struct CastingAbility {
+    pub ability: String,
+    pub aim: AbilityAim,
+    pub complete_tick: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastFailure {
+    UnknownAbility,
+    Dead,
+    Busy,
+    Cooldown,
+    NeedsTarget,
+    InvalidTarget,
+    OutOfRange,
+    Interrupted,
+}
+
+impl CastFailure {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CastFailure::UnknownAbility => "unknown_ability",
+            CastFailure::Dead => "dead",
+            CastFailure::Busy => "busy",
+            CastFailure::Cooldown => "cooldown",
+            CastFailure::NeedsTarget => "needs_target",
+            CastFailure::InvalidTarget => "invalid_target",
+            CastFailure::OutOfRange => "out_of_range",
+            CastFailure::Interrupted => "interrupted",
+        }
+    }
+}
+
+// ============================================================================
+// ABILITY RESOURCES
+// ============================================================================
+
+#[derive(Resource, Default)]
+pub struct AbilityDefinitions {
+    pub abilities: HashMap<String, AbilityDefinition>,
+}
+
+// ============================================================================
+// ABILITY MESSAGE KEYS
+// ============================================================================
+
+pub const ABILITY_KEY: &str = "ab";
+pub const STAGE_KEY: &str = "st";
+pub const TARGET_KEY: &str = "tg";
+pub const AIM_KEY: &str = "at";
+pub const CAST_TIME_KEY: &str = "ct";
+pub const HITS_KEY: &str = "h";
+pub const REASON_KEY: &str = "r";
+pub const PROJECTILE_KEY: &str = "pr";
+
+pub const STAGE_CAST: &str = "cast";
+pub const STAGE_HIT: &str = "hit";
+pub const STAGE_LAUNCH: &str = "launch";
+pub const STAGE_FAIL: &str = "fail";
@@ -0,0 +1,48 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::{AbilityDataFile, AbilityDefinitions};
+use systems::{ability_cast_request_system, ability_resolve_system};
+
+const DEFAULT_ABILITY_DATA_PATH: &str = "data/abilities/abilities.json";
+
+// Ability plugin: data-driven abilities cast by players, validated and resolved on the server
+pub struct AbilityPlugin {
+    pub data_path: String,
+}
+
+impl AbilityPlugin {
+    // ABILITY_DATA overrides the default ability file
+    pub fn from_env() -> Self {
+        let data_path = std::env::var("ABILITY_DATA").unwrap_or_else(|_| DEFAULT_ABILITY_DATA_PATH.to_string());
+        Self { data_path }
+    }
+}
+
+impl Plugin for AbilityPlugin {
+    fn build(&self, app: &mut App) {
+        let data = std::fs::read_to_string(&self.data_path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| serde_json::from_str::<AbilityDataFile>(&data).map_err(|e| e.to_string()));
+
+        let abilities = match data {
+            Ok(data) => {
+                println!("⚔️ Loaded {} abilities from {}", data.abilities.len(), self.data_path);
+                data.abilities
+            }
+            Err(e) => {
+                println!("❌ Failed to load ability data {}: {}", self.data_path, e);
+                Default::default()
+            }
+        };
+
+        app.insert_resource(AbilityDefinitions { abilities })
+            .add_systems(FixedUpdate, (
+                ability_cast_request_system,
+                ability_resolve_system,
+            ).chain()
+                .after(crate::ecs::systems::input_processing_system)
+                .before(crate::ecs::plugins::health::systems::apply_damage_system));
+    }
+}
@@ -0,0 +1,352 @@
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+use crate::ecs::components::*;
+use crate::ecs::plugins::health::components::{can_damage, DamageEvent, Dead, Health};
+use crate::ecs::plugins::lag_compensation::components::{rewound_position, AckedTick, LagCompensationConfig, PositionHistory};
+use crate::ecs::plugins::network::components::{
+    EntityUpdate, NetworkId, NetworkIdAllocator, NetworkMessage, NetworkUpdates, ViewRangeTracker, ABILITY_EVENT_TYPE,
+};
+use crate::ecs::plugins::party::components::Parties;
+use crate::ecs::plugins::projectile::components::{Projectile, ProjectileBundle};
+use crate::ecs::plugins::simulation::components::{SimulationTick, TICK_RATE_HZ};
+use crate::ecs::plugins::zone::components::ZoneId;
+use super::components::*;
+
+type CastRequestQuery<'w, 's> = Query<'w, 's, (
+    &'static Player,
+    &'static NetworkId,
+    &'static Position,
+    &'static ZoneId,
+    &'static ViewRangeTracker,
+    &'static mut AbilityCooldowns,
+    Has<Dead>,
+    Has<CastingAbility>,
+    Option<&'static AckedTick>,
+)>;
+
+type CastTargetQuery<'w, 's> = Query<'w, 's, (
+    Entity,
+    Option<&'static Player>,
+    &'static NetworkId,
+    &'static Position,
+    &'static ZoneId,
+    &'static Health,
+    Option<&'static PositionHistory>,
+)>;
+
+type CastingQuery<'w, 's> = Query<'w, 's, (
+    Entity,
+    &'static Player,
+    &'static CastingAbility,
+    &'static NetworkId,
+    &'static Position,
+    &'static ZoneId,
+    &'static ViewRangeTracker,
+    Has<Dead>,
+    Option<&'static AckedTick>,
+)>;
+
+type HitTargetQuery<'w, 's> = Query<'w, 's, (
+    Entity,
+    Option<&'static Player>,
+    &'static Position,
+    &'static ZoneId,
+    &'static Health,
+    Option<&'static NetworkId>,
+    Option<&'static PositionHistory>,
+)>;
+
+// ============================================================================
+// ABILITY HELPERS
+// ============================================================================
+
+// Aim as sent by the client, before the target's network ID is resolved
+enum RequestedAim {
+    Target(u32),
+    Direction(Vec2),
+}
+
+fn secs_to_ticks(secs: f32) -> u64 {
+    (secs as f64 * TICK_RATE_HZ).round() as u64
+}
+
+fn ability_message(caster: u32, ability: &str, stage: &str, fields: Vec<(&str, serde_json::Value)>) -> NetworkMessage {
+    let mut components = HashMap::new();
+    components.insert(ABILITY_KEY.to_string(), serde_json::Value::String(ability.to_string()));
+    components.insert(STAGE_KEY.to_string(), serde_json::Value::String(stage.to_string()));
+    for (key, value) in fields {
+        components.insert(key.to_string(), value);
+    }
+
+    NetworkMessage {
+        message_type: ABILITY_EVENT_TYPE.to_string(),
+        entity_updates: vec![EntityUpdate { network_id: caster, components }],
+        tick: None,
+    }
+}
+
+// Everyone who can see the caster sees its casts (the caster's own player included)
+fn send_to_viewers(network_updates: &mut NetworkUpdates, view_tracker: &ViewRangeTracker, message: NetworkMessage) {
+    for player_id in &view_tracker.players_in_view {
+        network_updates.player_messages.entry(*player_id).or_default().push(message.clone());
+    }
+}
+
+// Failures are only reported back to the casting player
+fn send_failure(network_updates: &mut NetworkUpdates, player: &Player, caster: u32, ability: &str, failure: CastFailure) {
+    let message = ability_message(caster, ability, STAGE_FAIL, vec![
+        (REASON_KEY, serde_json::Value::String(failure.as_str().to_string())),
+    ]);
+    network_updates.player_messages.entry(player.id).or_default().push(message);
+}
+
+fn aim_json(point: Vec2) -> serde_json::Value {
+    serde_json::json!([point.x.round(), point.y.round()])
+}
+
+// ============================================================================
+// ABILITY SYSTEMS
+// ============================================================================
+
+// Validates cast commands against the ability data, cooldowns and range, and starts
+// the cast. Rejected casts never touch the cooldown.
+#[allow(clippy::too_many_arguments)]
+pub fn ability_cast_request_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    definitions: Res<AbilityDefinitions>,
+    player_registry: Res<PlayerRegistry>,
+    mut network_updates: ResMut<NetworkUpdates>,
+    mut input_events: EventReader<InputCommandEvent>,
+    lag_compensation: Res<LagCompensationConfig>,
+    parties: Res<Parties>,
+    mut casters: CastRequestQuery,
+    targets: CastTargetQuery,
+) {
+    // A second cast from the same player this tick sees the first one as in progress
+    let mut started = HashSet::new();
+
+    for event in input_events.read() {
+        let (ability, aim) = match &event.command {
+            InputCommand::CastAtTarget { ability, target } => (ability, RequestedAim::Target(*target)),
+            InputCommand::CastInDirection { ability, direction } => (ability, RequestedAim::Direction(*direction)),
+            _ => continue,
+        };
+        let Some(caster_entity) = player_registry.get_player_entity(event.player_id) else {
+            continue;
+        };
+        let Ok((player, network_id, position, zone, view_tracker, mut cooldowns, dead, casting, acked)) = casters.get_mut(caster_entity) else {
+            continue;
+        };
+
+        let Some(definition) = definitions.abilities.get(ability) else {
+            send_failure(&mut network_updates, player, network_id.0, ability, CastFailure::UnknownAbility);
+            continue;
+        };
+        if dead {
+            send_failure(&mut network_updates, player, network_id.0, ability, CastFailure::Dead);
+            continue;
+        }
+        if casting || started.contains(&caster_entity) {
+            send_failure(&mut network_updates, player, network_id.0, ability, CastFailure::Busy);
+            continue;
+        }
+        if cooldowns.ready_tick.get(ability).is_some_and(|ready| tick.0 < *ready) {
+            send_failure(&mut network_updates, player, network_id.0, ability, CastFailure::Cooldown);
+            continue;
+        }
+
+        let caster_position = Vec2::new(position.x, position.y);
+        let (aim, aim_field) = match aim {
+            RequestedAim::Target(target_id) => {
+                let target = targets.iter()
+                    .find(|(entity, target_player, target_network_id, _, target_zone, health, _)| {
+                        target_network_id.0 == target_id
+                            && *entity != caster_entity
+                            && *target_zone == zone
+                            && !health.is_dead()
+                            && can_damage(&parties, Some(player.id), target_player.map(|target| target.id))
+                    });
+                let Some((target_entity, _, _, target_position, _, _, history)) = target else {
+                    send_failure(&mut network_updates, player, network_id.0, ability, CastFailure::InvalidTarget);
+                    continue;
+                };
+                // Range is checked against where the caster saw the target
+                let view_tick = lag_compensation.view_tick(tick.0, acked);
+                if caster_position.distance(rewound_position(history, target_position, view_tick)) > definition.range {
+                    send_failure(&mut network_updates, player, network_id.0, ability, CastFailure::OutOfRange);
+                    continue;
+                }
+                (AbilityAim::Target(target_entity), (TARGET_KEY, serde_json::Value::from(target_id)))
+            }
+            RequestedAim::Direction(direction) => {
+                if matches!(definition.shape, AbilityShape::Single) && definition.projectile.is_none() {
+                    send_failure(&mut network_updates, player, network_id.0, ability, CastFailure::NeedsTarget);
+                    continue;
+                }
+                let direction = direction.normalize_or_zero();
+                if direction == Vec2::ZERO {
+                    send_failure(&mut network_updates, player, network_id.0, ability, CastFailure::InvalidTarget);
+                    continue;
+                }
+                let aim_point = caster_position + direction * definition.range;
+                (AbilityAim::Direction(direction), (AIM_KEY, aim_json(aim_point)))
+            }
+        };
+
+        // The cooldown starts when the cast starts, so interrupted casts still cost it
+        cooldowns.ready_tick.insert(ability.clone(), tick.0 + secs_to_ticks(definition.cooldown_secs));
+        let cast_ticks = secs_to_ticks(definition.cast_time_secs);
+        commands.entity(caster_entity).insert(CastingAbility {
+            ability: ability.clone(),
+            aim,
+            complete_tick: tick.0 + cast_ticks,
+        });
+        started.insert(caster_entity);
+
+        // Instant casts only announce their result
+        if cast_ticks > 0 {
+            let message = ability_message(network_id.0, ability, STAGE_CAST, vec![
+                aim_field,
+                (CAST_TIME_KEY, serde_json::Value::from(definition.cast_time_secs)),
+            ]);
+            send_to_viewers(&mut network_updates, view_tracker, message);
+        }
+    }
+}
+
+// Finishes casts whose cast time is up: re-checks the target, collects everything
+// inside the ability's shape and sends the damage through the health pipeline
+#[allow(clippy::too_many_arguments)]
+pub fn ability_resolve_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    definitions: Res<AbilityDefinitions>,
+    mut network_updates: ResMut<NetworkUpdates>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut allocator: ResMut<NetworkIdAllocator>,
+    lag_compensation: Res<LagCompensationConfig>,
+    parties: Res<Parties>,
+    casters: CastingQuery,
+    targets: HitTargetQuery,
+) {
+    for (caster_entity, player, casting, network_id, position, zone, view_tracker, dead, acked) in casters.iter() {
+        if tick.0 < casting.complete_tick && !dead {
+            continue;
+        }
+        commands.entity(caster_entity).remove::<CastingAbility>();
+
+        let Some(definition) = definitions.abilities.get(&casting.ability) else {
+            continue;
+        };
+        if dead {
+            send_failure(&mut network_updates, player, network_id.0, &casting.ability, CastFailure::Interrupted);
+            continue;
+        }
+
+        // Everything the caster aims at is checked where the caster saw it
+        let caster_position = Vec2::new(position.x, position.y);
+        let view_tick = lag_compensation.view_tick(tick.0, acked);
+        let (aim_point, primary) = match casting.aim {
+            AbilityAim::Target(target) => {
+                let target_position = match targets.get(target) {
+                    // The caster may have changed zones or joined the target's party during the cast
+                    Ok((_, target_player, target_position, target_zone, health, _, history))
+                        if target_zone == zone
+                            && !health.is_dead()
+                            && can_damage(&parties, Some(player.id), target_player.map(|target| target.id)) =>
+                    {
+                        rewound_position(history, target_position, view_tick)
+                    }
+                    _ => {
+                        send_failure(&mut network_updates, player, network_id.0, &casting.ability, CastFailure::InvalidTarget);
+                        continue;
+                    }
+                };
+                // The target may have walked away during the cast
+                if caster_position.distance(target_position) > definition.range {
+                    send_failure(&mut network_updates, player, network_id.0, &casting.ability, CastFailure::OutOfRange);
+                    continue;
+                }
+                (target_position, Some(target))
+            }
+            AbilityAim::Direction(direction) => (caster_position + direction * definition.range, None),
+        };
+
+        // Projectiles fly for the ability's range and deal its damage to the first body they hit
+        if let Some(spec) = definition.projectile {
+            let direction = (aim_point - caster_position).normalize_or(Vec2::X);
+            let lifetime_ticks = ((definition.range / spec.speed) as f64 * TICK_RATE_HZ).ceil().max(1.0) as u64;
+            let projectile_network_id = allocator.allocate();
+            commands.spawn(ProjectileBundle::new(
+                Projectile {
+                    owner: caster_entity,
+                    owner_player: Some(player.id),
+                    damage: definition.damage,
+                    hit_radius: spec.hit_radius,
+                    expires_tick: tick.0 + lifetime_ticks,
+                    rewind_ticks: view_tick.map(|view_tick| tick.0 - view_tick).unwrap_or(0),
+                },
+                caster_position,
+                direction * spec.speed,
+                *zone,
+                projectile_network_id,
+            ));
+
+            let message = ability_message(network_id.0, &casting.ability, STAGE_LAUNCH, vec![
+                (AIM_KEY, aim_json(aim_point)),
+                (PROJECTILE_KEY, serde_json::Value::from(projectile_network_id)),
+            ]);
+            send_to_viewers(&mut network_updates, view_tracker, message);
+            continue;
+        }
+
+        let hits: Vec<(Entity, Option<u32>)> = match definition.shape {
+            AbilityShape::Single => primary
+                .into_iter()
+                .map(|target| (target, targets.get(target).ok().and_then(|(_, _, _, _, _, id, _)| id.map(|id| id.0))))
+                .collect(),
+            AbilityShape::Circle { radius } => targets.iter()
+                .filter(|(entity, target_player, target_position, target_zone, health, _, history)| {
+                    *entity != caster_entity
+                        && *target_zone == zone
+                        && !health.is_dead()
+                        && can_damage(&parties, Some(player.id), target_player.map(|target| target.id))
+                        && aim_point.distance(rewound_position(*history, target_position, view_tick)) <= radius
+                })
+                .map(|(entity, _, _, _, _, id, _)| (entity, id.map(|id| id.0)))
+                .collect(),
+            AbilityShape::Cone { angle_degrees } => {
+                let forward = (aim_point - caster_position).normalize_or_zero();
+                let min_dot = (angle_degrees.to_radians() / 2.0).cos();
+                targets.iter()
+                    .filter(|(entity, target_player, target_position, target_zone, health, _, history)| {
+                        let offset = rewound_position(*history, target_position, view_tick) - caster_position;
+                        *entity != caster_entity
+                            && *target_zone == zone
+                            && !health.is_dead()
+                            && can_damage(&parties, Some(player.id), target_player.map(|target| target.id))
+                            && offset.length() <= definition.range
+                            && offset.normalize_or_zero().dot(forward) >= min_dot
+                    })
+                    .map(|(entity, _, _, _, _, id, _)| (entity, id.map(|id| id.0)))
+                    .collect()
+            }
+        };
+
+        for (target, _) in &hits {
+            damage_events.send(DamageEvent { target: *target, amount: definition.damage, source: Some(caster_entity) });
+        }
+
+        // Hits are reported as [network_id, damage] pairs; non-networked entities are left out
+        let reported_hits: Vec<serde_json::Value> = hits.iter()
+            .filter_map(|(_, id)| *id)
+            .map(|id| serde_json::json!([id, definition.damage]))
+            .collect();
+        let message = ability_message(network_id.0, &casting.ability, STAGE_HIT, vec![
+            (AIM_KEY, aim_json(aim_point)),
+            (HITS_KEY, serde_json::Value::Array(reported_hits)),
+        ]);
+        send_to_viewers(&mut network_updates, view_tracker, message);
+    }
+}
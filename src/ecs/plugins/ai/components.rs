@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// ============================================================================
+// ARCHETYPE DATA
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdleBehavior {
+    #[default]
+    Stand,
+    Wander,
+    Patrol,
+}
+
+// How an NPC reacts to the nearest player inside its awareness radius
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerReaction {
+    #[default]
+    Ignore,
+    Chase,
+    Flee,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AiArchetype {
+    #[serde(default)]
+    pub idle: IdleBehavior,
+    #[serde(default)]
+    pub wander_radius: f32,
+    #[serde(default)]
+    pub patrol_route: Vec<[f32; 2]>,
+    #[serde(default)]
+    pub reaction: PlayerReaction,
+    #[serde(default)]
+    pub awareness_radius: f32,
+    // Distance from home that makes the NPC give up and return (0 = unlimited)
+    #[serde(default)]
+    pub leash_radius: f32,
+    pub think_interval_secs: f32,
+    pub max_speed: Option<f32>,
+    pub max_health: Option<f32>,
+    // Damage dealt to a chased player in reach, once per think
+    #[serde(default)]
+    pub attack_damage: f32,
+    // Loot table rolled on death
+    #[serde(default)]
+    pub loot_table: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NpcSpawn {
+    pub archetype: String,
+    pub position: [f32; 2],
+    #[serde(default = "default_spawn_count")]
+    pub count: u32,
+    #[serde(default = "default_spawn_networked")]
+    pub networked: bool,
+}
+
+fn default_spawn_count() -> u32 {
+    1
+}
+
+fn default_spawn_networked() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AiDataFile {
+    pub archetypes: HashMap<String, AiArchetype>,
+    #[serde(default)]
+    pub spawns: Vec<NpcSpawn>,
+}
+
+// ============================================================================
+// AI COMPONENTS
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AiState {
+    Idle,
+    Wandering,
+    Patrolling,
+    Chasing(Entity),
+    Fleeing(Entity),
+    ReturningHome,
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct AiBrain {
+    pub archetype: String,
+    pub state: AiState,
+    pub home: Vec2,
+    pub patrol_index: usize,
+    pub next_think_tick: u64,
+}
+
+// ============================================================================
+// AI RESOURCES
+// ============================================================================
+
+#[derive(Resource, Default)]
+pub struct AiArchetypes {
+    pub archetypes: HashMap<String, AiArchetype>,
+}
+
+#[derive(Resource, Default)]
+pub struct NpcSpawnList {
+    pub spawns: Vec<NpcSpawn>,
+}
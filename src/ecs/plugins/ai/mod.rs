@@ -0,0 +1,57 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::{AiArchetypes, AiDataFile, NpcSpawnList};
+use systems::{ai_think_system, attach_ai_brain_system, spawn_initial_npcs_system};
+
+const DEFAULT_AI_DATA_PATH: &str = "data/ai/archetypes.json";
+
+// AI plugin: data-driven state machines for NPC characters
+pub struct AiPlugin {
+    pub data_path: String,
+    // Replays already contain the initial NPC spawn events
+    pub spawn_initial_npcs: bool,
+}
+
+impl AiPlugin {
+    // AI_DATA overrides the default archetype file
+    pub fn from_env() -> Self {
+        let data_path = std::env::var("AI_DATA").unwrap_or_else(|_| DEFAULT_AI_DATA_PATH.to_string());
+        Self { data_path, spawn_initial_npcs: true }
+    }
+}
+
+impl Plugin for AiPlugin {
+    fn build(&self, app: &mut App) {
+        let data = std::fs::read_to_string(&self.data_path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| serde_json::from_str::<AiDataFile>(&data).map_err(|e| e.to_string()));
+
+        let (archetypes, spawns) = match data {
+            Ok(data) => {
+                println!("🧠 Loaded {} AI archetypes from {}", data.archetypes.len(), self.data_path);
+                (data.archetypes, data.spawns)
+            }
+            Err(e) => {
+                println!("❌ Failed to load AI data {}: {}", self.data_path, e);
+                Default::default()
+            }
+        };
+
+        app.insert_resource(AiArchetypes { archetypes })
+            .insert_resource(NpcSpawnList { spawns })
+            .add_systems(FixedUpdate, (
+                attach_ai_brain_system.after(crate::ecs::systems::character_spawn_system),
+                ai_think_system
+                    .after(attach_ai_brain_system)
+                    .after(crate::ecs::systems::character_spawn_system)
+                    .before(crate::ecs::plugins::pathfinding::systems::queue_path_requests_system)
+                    .before(crate::ecs::plugins::health::systems::apply_damage_system),
+            ));
+
+        if self.spawn_initial_npcs {
+            app.add_systems(PostStartup, spawn_initial_npcs_system);
+        }
+    }
+}
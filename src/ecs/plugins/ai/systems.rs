@@ -0,0 +1,249 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::ecs::components::*;
+use crate::ecs::plugins::health::components::{DamageEvent, Dead, Health};
+use crate::ecs::plugins::loot::components::DropsLoot;
+use crate::ecs::plugins::pathfinding::components::{PathFollower, PathRequestEvent};
+use crate::ecs::plugins::simulation::components::{SimulationRng, SimulationTick, TICK_RATE_HZ};
+use crate::ecs::plugins::zone::components::ZoneId;
+use super::components::*;
+
+type NewNpcQuery<'w, 's> = Query<'w, 's, (
+    Entity,
+    &'static Character,
+    &'static NpcArchetype,
+    &'static Position,
+    &'static mut CharacterProfile,
+    &'static mut Health,
+), Added<NpcArchetype>>;
+
+// Players NPCs can notice
+type PerceivedPlayerQuery<'w, 's> = Query<'w, 's, (
+    Entity,
+    &'static Player,
+    &'static Position,
+    &'static ZoneId,
+), (Without<AiBrain>, Without<Dead>)>;
+
+type ThinkingNpcQuery<'w, 's> = Query<'w, 's, (
+    Entity,
+    &'static Position,
+    &'static ZoneId,
+    &'static Velocity,
+    &'static mut AiBrain,
+    &'static mut PathFollower,
+    &'static mut DesiredVelocity,
+)>;
+
+// Chasers stop this close to their target instead of pushing into it
+const CHASE_STOP_DISTANCE: f32 = 40.0;
+const HOME_ARRIVE_DISTANCE: f32 = 16.0;
+// An NPC with waypoints left but slower than this at think time is blocked
+const STUCK_SPEED: f32 = 1.0;
+
+// ============================================================================
+// AI HELPERS
+// ============================================================================
+
+fn think_interval_ticks(archetype: &AiArchetype) -> u64 {
+    ((archetype.think_interval_secs as f64 * TICK_RATE_HZ).round() as u64).max(1)
+}
+
+fn clamp_to_world(point: Vec2, game_config: &GameConfig) -> Vec2 {
+    point.clamp(Vec2::ZERO, game_config.world_bounds)
+}
+
+fn stop(follower: &mut PathFollower, desired_velocity: &mut DesiredVelocity) {
+    follower.waypoints.clear();
+    desired_velocity.x = 0.0;
+    desired_velocity.y = 0.0;
+}
+
+// ============================================================================
+// AI SYSTEMS
+// ============================================================================
+
+// Spawn the NPCs listed in the AI data file, unless a snapshot already restored some
+pub fn spawn_initial_npcs_system(
+    spawn_list: Res<NpcSpawnList>,
+    characters: Query<&Character>,
+    mut spawn_events: EventWriter<CharacterSpawnEvent>,
+) {
+    if !characters.is_empty() {
+        return;
+    }
+
+    let mut character_id = 1;
+    for spawn in &spawn_list.spawns {
+        for _ in 0..spawn.count {
+            spawn_events.send(CharacterSpawnEvent {
+                character_id,
+                position: Some(Position { x: spawn.position[0], y: spawn.position[1] }),
+                archetype: Some(spawn.archetype.clone()),
+                networked: spawn.networked,
+            });
+            character_id += 1;
+        }
+    }
+
+    if character_id > 1 {
+        println!("🧠 Spawning {} NPCs from AI data", character_id - 1);
+    }
+}
+
+pub fn attach_ai_brain_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    archetypes: Res<AiArchetypes>,
+    mut query: NewNpcQuery,
+) {
+    for (entity, character, npc_archetype, position, mut profile, mut health) in query.iter_mut() {
+        let Some(archetype) = archetypes.archetypes.get(&npc_archetype.name) else {
+            println!("❌ Character {} has unknown AI archetype '{}'", character.id, npc_archetype.name);
+            continue;
+        };
+
+        if let Some(max_speed) = archetype.max_speed {
+            profile.max_speed = max_speed;
+        }
+        if let Some(max_health) = archetype.max_health {
+            health.max = max_health;
+            health.current = max_health;
+        }
+        if let Some(table) = &archetype.loot_table {
+            commands.entity(entity).insert(DropsLoot { table: table.clone() });
+        }
+
+        // Stagger think ticks so NPCs of one archetype don't all think on the same tick
+        let interval = think_interval_ticks(archetype);
+        commands.entity(entity).insert(AiBrain {
+            archetype: npc_archetype.name.clone(),
+            state: AiState::Idle,
+            home: Vec2::new(position.x, position.y),
+            patrol_index: 0,
+            next_think_tick: tick.0 + 1 + character.id as u64 % interval,
+        });
+    }
+}
+
+// Runs each NPC's state machine at its archetype's think rate. Movement goes through
+// PathFollower / DesiredVelocity, the same way player input drives players.
+#[allow(clippy::too_many_arguments)]
+pub fn ai_think_system(
+    tick: Res<SimulationTick>,
+    archetypes: Res<AiArchetypes>,
+    game_config: Res<GameConfig>,
+    mut rng: ResMut<SimulationRng>,
+    mut path_requests: EventWriter<PathRequestEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
+    players: PerceivedPlayerQuery,
+    mut npcs: ThinkingNpcQuery,
+) {
+    for (entity, position, zone, velocity, mut brain, mut follower, mut desired_velocity) in npcs.iter_mut() {
+        if tick.0 < brain.next_think_tick {
+            continue;
+        }
+        let Some(archetype) = archetypes.archetypes.get(&brain.archetype) else {
+            continue;
+        };
+        brain.next_think_tick = tick.0 + think_interval_ticks(archetype);
+
+        let current = Vec2::new(position.x, position.y);
+        let distance_from_home = current.distance(brain.home);
+
+        // Leash: give up on whatever we're doing once too far from home
+        if archetype.leash_radius > 0.0
+            && distance_from_home > archetype.leash_radius
+            && brain.state != AiState::ReturningHome
+        {
+            brain.state = AiState::ReturningHome;
+            path_requests.send(PathRequestEvent { entity, goal: brain.home });
+            continue;
+        }
+
+        if brain.state == AiState::ReturningHome {
+            if distance_from_home > HOME_ARRIVE_DISTANCE {
+                if follower.waypoints.is_empty() {
+                    path_requests.send(PathRequestEvent { entity, goal: brain.home });
+                }
+                continue;
+            }
+            brain.state = AiState::Idle;
+        }
+
+        // Perception: nearest player in the same zone inside the awareness radius (ties broken by player ID)
+        let nearest_player = if archetype.reaction == PlayerReaction::Ignore {
+            None
+        } else {
+            players.iter()
+                .filter(|(_, _, _, player_zone)| *player_zone == zone)
+                .map(|(player_entity, player, player_position, _)| {
+                    let player_position = Vec2::new(player_position.x, player_position.y);
+                    (player_entity, player.id, player_position, current.distance(player_position))
+                })
+                .filter(|(_, _, _, distance)| *distance <= archetype.awareness_radius)
+                .min_by(|a, b| a.3.total_cmp(&b.3).then(a.1.cmp(&b.1)))
+        };
+
+        match (archetype.reaction, nearest_player) {
+            (PlayerReaction::Chase, Some((target, _, target_position, distance))) => {
+                brain.state = AiState::Chasing(target);
+                if distance <= CHASE_STOP_DISTANCE {
+                    stop(&mut follower, &mut desired_velocity);
+                    if archetype.attack_damage > 0.0 {
+                        damage_events.send(DamageEvent { target, amount: archetype.attack_damage, source: Some(entity) });
+                    }
+                } else {
+                    path_requests.send(PathRequestEvent { entity, goal: target_position });
+                }
+                continue;
+            }
+            (PlayerReaction::Flee, Some((threat, _, threat_position, _))) => {
+                brain.state = AiState::Fleeing(threat);
+                let away = (current - threat_position).normalize_or(Vec2::X);
+                let goal = clamp_to_world(current + away * archetype.awareness_radius, &game_config);
+                path_requests.send(PathRequestEvent { entity, goal });
+                continue;
+            }
+            _ => {}
+        }
+
+        // Lost the target: head back home before resuming idle behavior
+        if matches!(brain.state, AiState::Chasing(_) | AiState::Fleeing(_)) {
+            brain.state = AiState::ReturningHome;
+            path_requests.send(PathRequestEvent { entity, goal: brain.home });
+            continue;
+        }
+
+        // Keep walking unless something (usually another body) is blocking the way
+        if !follower.waypoints.is_empty() {
+            if velocity.x.abs() + velocity.y.abs() >= STUCK_SPEED {
+                continue;
+            }
+            follower.waypoints.clear();
+        }
+
+        match archetype.idle {
+            IdleBehavior::Stand => {
+                brain.state = AiState::Idle;
+            }
+            IdleBehavior::Wander => {
+                brain.state = AiState::Wandering;
+                let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+                let distance = rng.gen_range(0.0..=archetype.wander_radius);
+                let goal = clamp_to_world(brain.home + Vec2::from_angle(angle) * distance, &game_config);
+                path_requests.send(PathRequestEvent { entity, goal });
+            }
+            IdleBehavior::Patrol => {
+                if archetype.patrol_route.is_empty() {
+                    brain.state = AiState::Idle;
+                    continue;
+                }
+                brain.state = AiState::Patrolling;
+                let point = archetype.patrol_route[brain.patrol_index % archetype.patrol_route.len()];
+                brain.patrol_index = (brain.patrol_index + 1) % archetype.patrol_route.len();
+                path_requests.send(PathRequestEvent { entity, goal: Vec2::new(point[0], point[1]) });
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+// Move and cast directions are unit vectors; allow a little slack for client float error
+pub const MAX_INPUT_MAGNITUDE: f32 = 1.1;
+pub const MAX_ABILITY_NAME_LENGTH: usize = 32;
+
+// ============================================================================
+// ANTI-CHEAT TYPES
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputAnomaly {
+    // NaN / infinite values or garbage no honest client sends
+    Malformed,
+    // Direction vectors longer than MAX_INPUT_MAGNITUDE
+    Oversized,
+    // More inputs per second than a client can legitimately produce
+    InputFlood,
+}
+
+impl InputAnomaly {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InputAnomaly::Malformed => "malformed input",
+            InputAnomaly::Oversized => "oversized input",
+            InputAnomaly::InputFlood => "input flood",
+        }
+    }
+
+    // How much each anomaly adds to a player's suspicion score
+    pub fn weight(&self) -> f32 {
+        match self {
+            InputAnomaly::Malformed => 5.0,
+            InputAnomaly::Oversized => 2.0,
+            InputAnomaly::InputFlood => 3.0,
+        }
+    }
+}
+
+// ============================================================================
+// ANTI-CHEAT EVENTS
+// ============================================================================
+
+// `score` is the player's running suspicion score after this anomaly
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SuspiciousActivityEvent {
+    pub player_id: u32,
+    pub anomaly: InputAnomaly,
+    pub score: f32,
+}
+
+// ============================================================================
+// ANTI-CHEAT RESOURCES
+// ============================================================================
+
+#[derive(Resource, Debug, Clone)]
+pub struct AntiCheatConfig {
+    pub max_inputs_per_sec: u32,
+    // Score at which a player is kicked (0 disables kicking)
+    pub kick_score: f32,
+    // Score forgiven per second of good behavior
+    pub score_decay_per_sec: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PlayerInputStats {
+    pub window_start_secs: f64,
+    pub inputs_in_window: u32,
+    pub flood_reported: bool,
+    pub score: f32,
+    pub last_score_update_secs: f64,
+    pub kicked: bool,
+}
+
+impl PlayerInputStats {
+    pub fn new(now_secs: f64) -> Self {
+        Self { window_start_secs: now_secs, last_score_update_secs: now_secs, ..Default::default() }
+    }
+
+    // Decay the score for the time since the last anomaly, then add this one
+    pub fn add_anomaly(&mut self, anomaly: InputAnomaly, now_secs: f64, decay_per_sec: f32) -> f32 {
+        let elapsed = (now_secs - self.last_score_update_secs) as f32;
+        self.score = (self.score - elapsed * decay_per_sec).max(0.0) + anomaly.weight();
+        self.last_score_update_secs = now_secs;
+        self.score
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct AntiCheatState {
+    pub players: HashMap<u32, PlayerInputStats>,
+}
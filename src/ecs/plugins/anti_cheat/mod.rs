@@ -0,0 +1,50 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::{AntiCheatConfig, AntiCheatState, SuspiciousActivityEvent};
+use systems::{forget_departed_players_system, input_validation_system, suspicious_activity_system};
+
+const DEFAULT_MAX_INPUTS_PER_SEC: u32 = 60;
+const DEFAULT_KICK_SCORE: f32 = 20.0;
+const DEFAULT_SCORE_DECAY_PER_SEC: f32 = 0.5;
+
+// Anti-cheat plugin: validates client inputs, tracks per-player anomalies and
+// kicks players whose suspicion score gets too high
+pub struct AntiCheatPlugin {
+    pub max_inputs_per_sec: u32,
+    pub kick_score: f32,
+}
+
+impl AntiCheatPlugin {
+    // ANTI_CHEAT_MAX_INPUTS_PER_SEC sets the input budget; ANTI_CHEAT_KICK_SCORE=0 disables kicking
+    pub fn from_env() -> Self {
+        let max_inputs_per_sec = std::env::var("ANTI_CHEAT_MAX_INPUTS_PER_SEC")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_MAX_INPUTS_PER_SEC);
+        let kick_score = std::env::var("ANTI_CHEAT_KICK_SCORE")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_KICK_SCORE);
+
+        Self { max_inputs_per_sec, kick_score }
+    }
+}
+
+impl Plugin for AntiCheatPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AntiCheatConfig {
+                max_inputs_per_sec: self.max_inputs_per_sec,
+                kick_score: self.kick_score,
+                score_decay_per_sec: DEFAULT_SCORE_DECAY_PER_SEC,
+            })
+            .insert_resource(AntiCheatState::default())
+            .add_event::<SuspiciousActivityEvent>()
+            .add_systems(Update, (
+                input_validation_system.after(crate::ecs::plugins::websocket::systems::handle_websocket_messages),
+                suspicious_activity_system.after(input_validation_system),
+                forget_departed_players_system.after(suspicious_activity_system),
+            ));
+    }
+}
@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+use crate::ecs::components::*;
+use crate::ecs::plugins::shard::components::PendingHandoff;
+use super::components::*;
+
+// ============================================================================
+// INPUT VALIDATION
+// ============================================================================
+
+fn validate_ability_name(ability: &str) -> Result<(), InputAnomaly> {
+    if ability.is_empty() || ability.len() > MAX_ABILITY_NAME_LENGTH {
+        return Err(InputAnomaly::Malformed);
+    }
+    Ok(())
+}
+
+fn validate_direction(direction: Vec2) -> Result<(), InputAnomaly> {
+    if !direction.is_finite() {
+        return Err(InputAnomaly::Malformed);
+    }
+    if direction.length() > MAX_INPUT_MAGNITUDE {
+        return Err(InputAnomaly::Oversized);
+    }
+    Ok(())
+}
+
+pub fn validate_input(command: &InputCommand) -> Result<(), InputAnomaly> {
+    match command {
+        InputCommand::Move { direction } => validate_direction(*direction),
+        InputCommand::CastInDirection { ability, direction } => {
+            validate_ability_name(ability)?;
+            validate_direction(*direction)
+        }
+        InputCommand::CastAtTarget { ability, .. } => validate_ability_name(ability),
+        // Chat text limits are enforced (and reported back) by the chat plugin
+        InputCommand::Chat { .. } => Ok(()),
+        InputCommand::Stop | InputCommand::Ack { .. } | InputCommand::Party(_) | InputCommand::Inventory(_) => Ok(()),
+    }
+}
+
+// ============================================================================
+// ANTI-CHEAT SYSTEMS
+// ============================================================================
+
+// Screens client inputs before the simulation sees them: malformed inputs and
+// inputs over the per-second budget are dropped and counted against the player.
+// Movement itself is simulated server-side from these directions, so there are no
+// client-reported positions or speeds to check.
+#[allow(clippy::too_many_arguments)]
+pub fn input_validation_system(
+    time: Res<Time>,
+    config: Res<AntiCheatConfig>,
+    mut state: ResMut<AntiCheatState>,
+    player_registry: Res<PlayerRegistry>,
+    mut client_inputs: EventReader<ClientInputEvent>,
+    mut input_events: EventWriter<InputCommandEvent>,
+    mut suspicious_events: EventWriter<SuspiciousActivityEvent>,
+    handing_off: Query<(), With<PendingHandoff>>,
+) {
+    let now = time.elapsed_secs_f64();
+
+    for event in client_inputs.read() {
+        let stats = state.players.entry(event.player_id).or_insert_with(|| PlayerInputStats::new(now));
+        // Nothing a kicked player sends reaches the simulation while their connection closes,
+        // and nothing from a player being handed off, whose state is already on its way
+        let handing_off = player_registry.get_player_entity(event.player_id)
+            .is_some_and(|entity| handing_off.contains(entity));
+        if stats.kicked || handing_off {
+            continue;
+        }
+
+        if now - stats.window_start_secs >= 1.0 {
+            stats.window_start_secs = now;
+            stats.inputs_in_window = 0;
+            stats.flood_reported = false;
+        }
+        stats.inputs_in_window += 1;
+
+        let result = if stats.inputs_in_window > config.max_inputs_per_sec {
+            Err(InputAnomaly::InputFlood)
+        } else {
+            validate_input(&event.command)
+        };
+
+        match result {
+            Ok(()) => {
+                input_events.send(InputCommandEvent { player_id: event.player_id, command: event.command.clone() });
+            }
+            Err(anomaly) => {
+                // Every input over the budget is dropped, but a flood only counts once per window
+                if anomaly == InputAnomaly::InputFlood {
+                    if stats.flood_reported {
+                        continue;
+                    }
+                    stats.flood_reported = true;
+                }
+                let score = stats.add_anomaly(anomaly, now, config.score_decay_per_sec);
+                suspicious_events.send(SuspiciousActivityEvent { player_id: event.player_id, anomaly, score });
+            }
+        }
+    }
+}
+
+pub fn suspicious_activity_system(
+    config: Res<AntiCheatConfig>,
+    mut state: ResMut<AntiCheatState>,
+    mut suspicious_events: EventReader<SuspiciousActivityEvent>,
+    mut kick_events: EventWriter<PlayerKickEvent>,
+) {
+    for event in suspicious_events.read() {
+        println!("🚨 Player {} {} (suspicion score {:.1})", event.player_id, event.anomaly.as_str(), event.score);
+
+        let Some(stats) = state.players.get_mut(&event.player_id) else {
+            continue;
+        };
+        if config.kick_score > 0.0 && event.score >= config.kick_score && !stats.kicked {
+            stats.kicked = true;
+            kick_events.send(PlayerKickEvent {
+                player_id: event.player_id,
+                reason: format!("suspicious activity ({})", event.anomaly.as_str()),
+            });
+        }
+    }
+}
+
+pub fn forget_departed_players_system(
+    mut state: ResMut<AntiCheatState>,
+    mut despawn_events: EventReader<PlayerDespawnEvent>,
+) {
+    for event in despawn_events.read() {
+        state.players.remove(&event.player_id);
+    }
+}
@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use crate::ecs::plugins::network::components::EntityUpdate;
+
+// ============================================================================
+// CHAT TYPES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ChatChannel {
+    // Heard by every player the speaker would come into view for
+    Say,
+    Global,
+    // Private message to a player ID
+    Whisper { to: u32 },
+    Party,
+}
+
+impl ChatChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChatChannel::Say => "say",
+            ChatChannel::Global => "global",
+            ChatChannel::Whisper { .. } => "whisper",
+            ChatChannel::Party => "party",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatFailure {
+    Empty,
+    TooLong,
+    RateLimited,
+    Blocked,
+    UnknownPlayer,
+    NoParty,
+}
+
+impl ChatFailure {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChatFailure::Empty => "empty",
+            ChatFailure::TooLong => "too_long",
+            ChatFailure::RateLimited => "rate_limited",
+            ChatFailure::Blocked => "blocked",
+            ChatFailure::UnknownPlayer => "unknown_player",
+            ChatFailure::NoParty => "no_party",
+        }
+    }
+}
+
+// ============================================================================
+// CHAT FILTERS
+// ============================================================================
+
+pub enum ChatFilterVerdict {
+    Allow,
+    // Deliver the rewritten text instead
+    Replace(String),
+    Block,
+}
+
+// Hook for moderating chat text; filters run in order on every accepted message
+pub trait ChatFilter: Send + Sync + 'static {
+    fn check(&self, player_id: u32, text: &str) -> ChatFilterVerdict;
+}
+
+// Masks (or blocks messages containing) words from a fixed list, case-insensitively
+pub struct WordListFilter {
+    pub words: HashSet<String>,
+    pub block: bool,
+}
+
+impl ChatFilter for WordListFilter {
+    fn check(&self, _player_id: u32, text: &str) -> ChatFilterVerdict {
+        let mut filtered = String::with_capacity(text.len());
+        let mut word = String::new();
+        let mut matched = false;
+
+        // A trailing separator flushes the last word
+        for c in text.chars().chain(std::iter::once(' ')) {
+            if c.is_alphanumeric() {
+                word.push(c);
+                continue;
+            }
+            if !word.is_empty() {
+                if self.words.contains(&word.to_lowercase()) {
+                    matched = true;
+                    filtered.extend(std::iter::repeat_n('*', word.chars().count()));
+                } else {
+                    filtered.push_str(&word);
+                }
+                word.clear();
+            }
+            filtered.push(c);
+        }
+        filtered.pop();
+
+        match (matched, self.block) {
+            (false, _) => ChatFilterVerdict::Allow,
+            (true, true) => ChatFilterVerdict::Block,
+            (true, false) => ChatFilterVerdict::Replace(filtered),
+        }
+    }
+}
+
+// ============================================================================
+// CHAT COMPONENTS
+// ============================================================================
+
+// Ticks of the player's recently accepted messages, for rate limiting
+#[derive(Component, Debug, Clone, Default)]
+pub struct ChatRateLimit {
+    pub recent_ticks: VecDeque<u64>,
+}
+
+// ============================================================================
+// CHAT RESOURCES
+// ============================================================================
+
+#[derive(Resource, Debug, Clone)]
+pub struct ChatConfig {
+    pub max_length: usize,
+    pub max_messages: usize,
+    pub rate_window_ticks: u64,
+    pub history_size: usize,
+}
+
+#[derive(Resource, Default)]
+pub struct ChatFilters {
+    pub filters: Vec<Box<dyn ChatFilter>>,
+}
+
+// Recent global messages, replayed to players when they join
+#[derive(Resource, Default)]
+pub struct ChatHistory {
+    pub global: VecDeque<EntityUpdate>,
+}
+
+// ============================================================================
+// CHAT MESSAGE KEYS
+// ============================================================================
+
+pub const CHANNEL_KEY: &str = "ch";
+pub const TEXT_KEY: &str = "tx";
+pub const SPEAKER_KEY: &str = "pl";
+pub const RECIPIENT_KEY: &str = "to";
+pub const REASON_KEY: &str = "r";
@@ -0,0 +1,80 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::{ChatConfig, ChatFilter, ChatFilters, ChatHistory, WordListFilter};
+use systems::{chat_history_sync_system, chat_system};
+use crate::ecs::plugins::simulation::components::TICK_RATE_HZ;
+
+const DEFAULT_MAX_LENGTH: usize = 200;
+const DEFAULT_MAX_MESSAGES: usize = 5;
+const DEFAULT_RATE_WINDOW_SECS: f64 = 10.0;
+const DEFAULT_HISTORY_SIZE: usize = 50;
+
+// Chat plugin: say, global, whisper and party channels with length and rate
+// limits, pluggable text filters and global history for late joiners
+pub struct ChatPlugin {
+    pub max_length: usize,
+    pub max_messages: usize,
+    pub rate_window_secs: f64,
+    pub history_size: usize,
+    pub blocked_words: Vec<String>,
+    pub block_filtered: bool,
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+impl ChatPlugin {
+    // CHAT_MAX_MESSAGES messages are allowed per CHAT_RATE_WINDOW_SECS; words in
+    // CHAT_BLOCKED_WORDS (comma separated) are masked, or rejected with CHAT_FILTER_BLOCK=1
+    pub fn from_env() -> Self {
+        let blocked_words = std::env::var("CHAT_BLOCKED_WORDS")
+            .map(|words| {
+                words.split(',')
+                    .map(|word| word.trim().to_lowercase())
+                    .filter(|word| !word.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            max_length: env_or("CHAT_MAX_LENGTH", DEFAULT_MAX_LENGTH),
+            max_messages: env_or("CHAT_MAX_MESSAGES", DEFAULT_MAX_MESSAGES),
+            rate_window_secs: env_or("CHAT_RATE_WINDOW_SECS", DEFAULT_RATE_WINDOW_SECS),
+            history_size: env_or("CHAT_HISTORY_SIZE", DEFAULT_HISTORY_SIZE),
+            blocked_words,
+            block_filtered: std::env::var("CHAT_FILTER_BLOCK").is_ok_and(|value| value == "1"),
+        }
+    }
+}
+
+impl Plugin for ChatPlugin {
+    fn build(&self, app: &mut App) {
+        let mut filters: Vec<Box<dyn ChatFilter>> = Vec::new();
+        if !self.blocked_words.is_empty() {
+            println!("🤐 Chat filter loaded with {} blocked words", self.blocked_words.len());
+            filters.push(Box::new(WordListFilter {
+                words: self.blocked_words.iter().cloned().collect(),
+                block: self.block_filtered,
+            }));
+        }
+
+        app.insert_resource(ChatConfig {
+                max_length: self.max_length,
+                max_messages: self.max_messages,
+                rate_window_ticks: (self.rate_window_secs * TICK_RATE_HZ).round() as u64,
+                history_size: self.history_size,
+            })
+            .insert_resource(ChatFilters { filters })
+            .insert_resource(ChatHistory::default())
+            .add_systems(FixedUpdate, (
+                chat_system.after(crate::ecs::systems::input_processing_system),
+                chat_history_sync_system.after(crate::ecs::systems::player_spawn_system),
+            ));
+    }
+}
@@ -0,0 +1,174 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::ecs::components::*;
+use crate::ecs::plugins::network::components::{EntityUpdate, NetworkId, NetworkMessage, NetworkUpdates, CHAT_TYPE};
+use crate::ecs::plugins::party::components::Parties;
+use crate::ecs::plugins::simulation::components::SimulationTick;
+use crate::ecs::plugins::zone::components::ZoneId;
+use super::components::*;
+
+// ============================================================================
+// CHAT MESSAGES
+// ============================================================================
+
+// Chat updates are keyed by the speaker's network ID
+fn chat_update(speaker: u32, channel: &ChatChannel, fields: Vec<(&str, serde_json::Value)>) -> EntityUpdate {
+    let mut components = HashMap::new();
+    components.insert(CHANNEL_KEY.to_string(), serde_json::Value::String(channel.as_str().to_string()));
+    for (key, value) in fields {
+        components.insert(key.to_string(), value);
+    }
+    EntityUpdate { network_id: speaker, components }
+}
+
+fn chat_message(entity_updates: Vec<EntityUpdate>) -> NetworkMessage {
+    NetworkMessage {
+        message_type: CHAT_TYPE.to_string(),
+        entity_updates,
+        tick: None,
+    }
+}
+
+fn send_failure(network_updates: &mut NetworkUpdates, player_id: u32, speaker: u32, channel: &ChatChannel, failure: ChatFailure) {
+    let update = chat_update(speaker, channel, vec![
+        (REASON_KEY, serde_json::Value::String(failure.as_str().to_string())),
+    ]);
+    network_updates.player_messages.entry(player_id).or_default().push(chat_message(vec![update]));
+}
+
+// ============================================================================
+// CHAT SYSTEMS
+// ============================================================================
+
+// Validates, rate limits and filters chat messages, then delivers them to the
+// channel's recipients. Rejections are reported only to the speaker.
+#[allow(clippy::too_many_arguments)]
+pub fn chat_system(
+    tick: Res<SimulationTick>,
+    config: Res<ChatConfig>,
+    filters: Res<ChatFilters>,
+    mut history: ResMut<ChatHistory>,
+    player_registry: Res<PlayerRegistry>,
+    parties: Res<Parties>,
+    mut network_updates: ResMut<NetworkUpdates>,
+    mut input_events: EventReader<InputCommandEvent>,
+    mut speakers: Query<(&NetworkId, &Position, &ZoneId, &mut ChatRateLimit)>,
+    listeners: Query<(&Player, &Position, &ZoneId, &ViewDistance)>,
+) {
+    for event in input_events.read() {
+        let InputCommand::Chat { channel, text } = &event.command else {
+            continue;
+        };
+        let Some(speaker_entity) = player_registry.get_player_entity(event.player_id) else {
+            continue;
+        };
+        let Ok((network_id, position, zone, mut rate_limit)) = speakers.get_mut(speaker_entity) else {
+            continue;
+        };
+
+        let text = text.trim();
+        if text.is_empty() {
+            send_failure(&mut network_updates, event.player_id, network_id.0, channel, ChatFailure::Empty);
+            continue;
+        }
+        if text.chars().count() > config.max_length {
+            send_failure(&mut network_updates, event.player_id, network_id.0, channel, ChatFailure::TooLong);
+            continue;
+        }
+
+        while rate_limit.recent_ticks.front().is_some_and(|sent| tick.0 - sent >= config.rate_window_ticks) {
+            rate_limit.recent_ticks.pop_front();
+        }
+        if rate_limit.recent_ticks.len() >= config.max_messages {
+            send_failure(&mut network_updates, event.player_id, network_id.0, channel, ChatFailure::RateLimited);
+            continue;
+        }
+        // Every message past the rate check counts, even if it is filtered or undeliverable
+        rate_limit.recent_ticks.push_back(tick.0);
+
+        let mut text = text.to_string();
+        let mut blocked = false;
+        for filter in &filters.filters {
+            match filter.check(event.player_id, &text) {
+                ChatFilterVerdict::Allow => {}
+                ChatFilterVerdict::Replace(filtered) => text = filtered,
+                ChatFilterVerdict::Block => {
+                    blocked = true;
+                    break;
+                }
+            }
+        }
+        if blocked {
+            send_failure(&mut network_updates, event.player_id, network_id.0, channel, ChatFailure::Blocked);
+            continue;
+        }
+
+        let speaker_position = Vec2::new(position.x, position.y);
+        let recipients: Vec<u32> = match channel {
+            ChatChannel::Say => listeners.iter()
+                .filter(|(_, listener_position, listener_zone, view_distance)| {
+                    *listener_zone == zone
+                        && Vec2::new(listener_position.x, listener_position.y).distance(speaker_position) <= view_distance.enter_range()
+                })
+                .map(|(player, _, _, _)| player.id)
+                .collect(),
+            ChatChannel::Global => listeners.iter().map(|(player, _, _, _)| player.id).collect(),
+            ChatChannel::Whisper { to } => {
+                if player_registry.get_player_entity(*to).is_none() {
+                    send_failure(&mut network_updates, event.player_id, network_id.0, channel, ChatFailure::UnknownPlayer);
+                    continue;
+                }
+                // The speaker gets an echo of their own whisper
+                let mut recipients = vec![*to];
+                if *to != event.player_id {
+                    recipients.push(event.player_id);
+                }
+                recipients
+            }
+            ChatChannel::Party => {
+                let Some((_, party)) = parties.party_of(event.player_id) else {
+                    send_failure(&mut network_updates, event.player_id, network_id.0, channel, ChatFailure::NoParty);
+                    continue;
+                };
+                party.members.clone()
+            }
+        };
+
+        println!("💬 [{}] Player {}: {}", channel.as_str(), event.player_id, text);
+
+        let mut fields = vec![
+            (SPEAKER_KEY, serde_json::Value::from(event.player_id)),
+            (TEXT_KEY, serde_json::Value::String(text)),
+        ];
+        if let ChatChannel::Whisper { to } = channel {
+            fields.push((RECIPIENT_KEY, serde_json::Value::from(*to)));
+        }
+        let update = chat_update(network_id.0, channel, fields);
+
+        for recipient in recipients {
+            network_updates.player_messages.entry(recipient).or_default().push(chat_message(vec![update.clone()]));
+        }
+
+        if matches!(channel, ChatChannel::Global) {
+            history.global.push_back(update);
+            while history.global.len() > config.history_size {
+                history.global.pop_front();
+            }
+        }
+    }
+}
+
+// Sends new players the recent global chat in one message, after their welcome
+pub fn chat_history_sync_system(
+    history: Res<ChatHistory>,
+    mut network_updates: ResMut<NetworkUpdates>,
+    mut spawn_events: EventReader<PlayerSpawnEvent>,
+) {
+    for event in spawn_events.read() {
+        if history.global.is_empty() {
+            continue;
+        }
+        let message = chat_message(history.global.iter().cloned().collect());
+        network_updates.player_messages.entry(event.player_id).or_default().push(message);
+    }
+}
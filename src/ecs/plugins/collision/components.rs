@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+// ============================================================================
+// COLLISION LAYERS
+// ============================================================================
+
+pub const COLLISION_LAYER_PLAYER: u32 = 1 << 0;
+pub const COLLISION_LAYER_CHARACTER: u32 = 1 << 1;
+pub const COLLISION_LAYER_PORTAL: u32 = 1 << 2;
+pub const COLLISION_LAYER_ALL: u32 = u32::MAX;
+
+// ============================================================================
+// COLLISION COMPONENTS
+// ============================================================================
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Collider {
+    pub radius: f32,
+    // Layers this collider belongs to
+    pub layer: u32,
+    // Layers this collider interacts with
+    pub mask: u32,
+    // Sensors report enter/exit events instead of pushing bodies apart
+    pub sensor: bool,
+}
+
+impl Collider {
+    pub fn solid(radius: f32, layer: u32, mask: u32) -> Self {
+        Self { radius, layer, mask, sensor: false }
+    }
+
+    // Sensors don't need a Velocity, so static triggers can be placed without one
+    pub fn sensor(radius: f32, layer: u32, mask: u32) -> Self {
+        Self { radius, layer, mask, sensor: true }
+    }
+
+    pub fn interacts_with(&self, other: &Collider) -> bool {
+        (self.layer & other.mask) != 0 && (other.layer & self.mask) != 0
+    }
+}
+
+// ============================================================================
+// COLLISION EVENTS
+// ============================================================================
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct TriggerEnterEvent {
+    pub sensor: Entity,
+    pub other: Entity,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct TriggerExitEvent {
+    pub sensor: Entity,
+    pub other: Entity,
+}
+
+// ============================================================================
+// COLLISION RESOURCES
+// ============================================================================
+
+// Uniform grid broad phase, rebuilt every tick. Cells store indices into the
+// tick's collider list so pair order follows query order and stays deterministic.
+#[derive(Resource)]
+pub struct SpatialHashGrid {
+    pub cell_size: f32,
+    pub cells: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl Default for SpatialHashGrid {
+    fn default() -> Self {
+        Self {
+            cell_size: 64.0,
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl SpatialHashGrid {
+    pub fn cell_of(&self, position: Vec2) -> (i32, i32) {
+        (
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    // Drops every cell, so cells bodies have moved out of don't pile up
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    // Inserts an index into every cell overlapped by the circle's bounding box
+    pub fn insert(&mut self, index: usize, position: Vec2, radius: f32) {
+        let (min_x, min_y) = self.cell_of(position - Vec2::splat(radius));
+        let (max_x, max_y) = self.cell_of(position + Vec2::splat(radius));
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                self.cells.entry((x, y)).or_default().push(index);
+            }
+        }
+    }
+}
+
+// Sensor overlaps from the previous tick, used to detect enter/exit transitions
+#[derive(Resource, Default)]
+pub struct SensorContacts {
+    pub contacts: HashSet<(Entity, Entity)>,
+}
@@ -0,0 +1,21 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::{SensorContacts, SpatialHashGrid, TriggerEnterEvent, TriggerExitEvent};
+use systems::collision_resolution_system;
+
+// Collision plugin: circle colliders resolved between movement and world boundaries
+pub struct CollisionPlugin;
+
+impl Plugin for CollisionPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SpatialHashGrid::default())
+            .insert_resource(SensorContacts::default())
+            .add_event::<TriggerEnterEvent>()
+            .add_event::<TriggerExitEvent>()
+            .add_systems(FixedUpdate, collision_resolution_system
+                .after(crate::ecs::systems::movement_system)
+                .before(crate::ecs::systems::boundary_system));
+    }
+}
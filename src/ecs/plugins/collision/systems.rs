@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use std::collections::HashSet;
+use crate::ecs::components::{Position, Velocity};
+use crate::ecs::plugins::zone::components::ZoneId;
+use super::components::*;
+
+// ============================================================================
+// COLLISION SYSTEMS
+// ============================================================================
+
+// Separates overlapping solid bodies, removes the velocity component pushing into
+// the other body, and tracks sensor overlaps for enter/exit events. Bodies without
+// a Velocity (static sensors) are never moved.
+pub fn collision_resolution_system(
+    mut grid: ResMut<SpatialHashGrid>,
+    mut sensor_contacts: ResMut<SensorContacts>,
+    mut enter_events: EventWriter<TriggerEnterEvent>,
+    mut exit_events: EventWriter<TriggerExitEvent>,
+    mut query: Query<(Entity, &mut Position, Option<&mut Velocity>, &Collider, &ZoneId)>,
+) {
+    // Broad phase: bucket every collider into grid cells
+    let bodies: Vec<(Entity, Vec2, Collider, ZoneId)> = query.iter()
+        .map(|(entity, position, _, collider, zone)| (entity, Vec2::new(position.x, position.y), *collider, *zone))
+        .collect();
+
+    grid.clear();
+    for (index, (_, position, collider, _)) in bodies.iter().enumerate() {
+        grid.insert(index, *position, collider.radius);
+    }
+
+    // Narrow phase: test each candidate pair once
+    let mut corrections = vec![Vec2::ZERO; bodies.len()];
+    let mut normals: Vec<Vec<Vec2>> = vec![Vec::new(); bodies.len()];
+    let mut current_contacts = Vec::new();
+    let mut tested = HashSet::new();
+
+    for (a, (entity_a, position_a, collider_a, zone_a)) in bodies.iter().enumerate() {
+        let (min_x, min_y) = grid.cell_of(*position_a - Vec2::splat(collider_a.radius));
+        let (max_x, max_y) = grid.cell_of(*position_a + Vec2::splat(collider_a.radius));
+
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                let Some(cell) = grid.cells.get(&(x, y)) else {
+                    continue;
+                };
+
+                for &b in cell {
+                    if b <= a || !tested.insert((a, b)) {
+                        continue;
+                    }
+
+                    // Zones share coordinates, so bodies in different zones can overlap
+                    let (entity_b, position_b, collider_b, zone_b) = &bodies[b];
+                    if zone_a != zone_b || !collider_a.interacts_with(collider_b) {
+                        continue;
+                    }
+
+                    let offset = *position_b - *position_a;
+                    let min_distance = collider_a.radius + collider_b.radius;
+                    let distance_squared = offset.length_squared();
+                    if distance_squared >= min_distance * min_distance {
+                        continue;
+                    }
+
+                    if collider_a.sensor || collider_b.sensor {
+                        if collider_a.sensor {
+                            current_contacts.push((*entity_a, *entity_b));
+                        }
+                        if collider_b.sensor {
+                            current_contacts.push((*entity_b, *entity_a));
+                        }
+                        continue;
+                    }
+
+                    // Coincident bodies get pushed apart along a fixed axis
+                    let distance = distance_squared.sqrt();
+                    let normal = if distance > f32::EPSILON { offset / distance } else { Vec2::X };
+                    let push = normal * (min_distance - distance) * 0.5;
+
+                    corrections[a] -= push;
+                    corrections[b] += push;
+                    normals[a].push(normal);
+                    normals[b].push(-normal);
+                }
+            }
+        }
+    }
+
+    // Apply corrections after all pairs are tested so results don't depend on pair order
+    for (index, (entity, _, _, _)) in bodies.iter().enumerate() {
+        if corrections[index] == Vec2::ZERO && normals[index].is_empty() {
+            continue;
+        }
+        let Ok((_, mut position, Some(mut velocity), _, _)) = query.get_mut(*entity) else {
+            continue;
+        };
+
+        position.x += corrections[index].x;
+        position.y += corrections[index].y;
+
+        for normal in &normals[index] {
+            let into_other = velocity.x * normal.x + velocity.y * normal.y;
+            if into_other > 0.0 {
+                velocity.x -= normal.x * into_other;
+                velocity.y -= normal.y * into_other;
+            }
+        }
+    }
+
+    // Sensor enter/exit transitions
+    for &(sensor, other) in &current_contacts {
+        if !sensor_contacts.contacts.contains(&(sensor, other)) {
+            enter_events.send(TriggerEnterEvent { sensor, other });
+        }
+    }
+    let current: HashSet<(Entity, Entity)> = current_contacts.into_iter().collect();
+    for &(sensor, other) in sensor_contacts.contacts.difference(&current) {
+        exit_events.send(TriggerExitEvent { sensor, other });
+    }
+    sensor_contacts.contacts = current;
+}
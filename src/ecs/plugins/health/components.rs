@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::ecs::plugins::party::components::Parties;
+
+// ============================================================================
+// HEALTH COMPONENTS
+// ============================================================================
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+    // Restored in pulses while alive and below max
+    pub regen_per_sec: f32,
+}
+
+impl Health {
+    pub fn new(max: f32, regen_per_sec: f32) -> Self {
+        Self { current: max, max, regen_per_sec }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+}
+
+// Marks a dead player waiting to respawn (dead NPCs are despawned instead)
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Dead {
+    pub respawn_tick: u64,
+}
+
+// Health as saved with a player's account, so logging off neither heals nor revives
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SavedHealth {
+    pub current: f32,
+    // Ticks a dead player had left before respawning
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub respawn_in_ticks: Option<u64>,
+}
+
+impl SavedHealth {
+    pub fn new(health: &Health, dead: Option<&Dead>, tick: u64) -> Self {
+        Self {
+            current: health.current,
+            respawn_in_ticks: dead.map(|dead| dead.respawn_tick.saturating_sub(tick)),
+        }
+    }
+
+    // Applies the saved health to a returning player, who stays dead for the rest of
+    // their respawn delay. Returns the Dead marker they should have, if any.
+    pub fn restore(&self, health: &mut Health, tick: u64) -> Option<Dead> {
+        health.current = self.current.clamp(0.0, health.max);
+        health.is_dead().then(|| Dead { respawn_tick: tick + self.respawn_in_ticks.unwrap_or(0) })
+    }
+}
+
+// ============================================================================
+// HEALTH EVENTS
+// ============================================================================
+
+// `source` is the attacking entity, or None for environmental damage
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub amount: f32,
+    pub source: Option<Entity>,
+}
+
+// Whether an attacker may damage a target, each given as a player ID or None for an
+// NPC: players never hurt themselves or their party, and NPCs never hurt each other
+pub fn can_damage(parties: &Parties, attacker: Option<u32>, target: Option<u32>) -> bool {
+    match (attacker, target) {
+        (Some(attacker), Some(target)) => attacker != target && !parties.same_party(attacker, target),
+        (None, None) => false,
+        _ => true,
+    }
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct HealEvent {
+    pub target: Entity,
+    pub amount: f32,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DeathEvent {
+    pub entity: Entity,
+    pub killer: Option<Entity>,
+}
+
+// ============================================================================
+// HEALTH RESOURCES
+// ============================================================================
+
+#[derive(Resource, Debug, Clone)]
+pub struct HealthConfig {
+    pub respawn_delay_ticks: u64,
+    // Defaults to the center of the world when unset
+    pub respawn_point: Option<Vec2>,
+}
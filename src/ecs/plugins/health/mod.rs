@@ -0,0 +1,52 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::{DamageEvent, DeathEvent, HealEvent, HealthConfig};
+use systems::{apply_damage_system, apply_heal_system, death_system, health_regen_system, player_respawn_system};
+use crate::ecs::plugins::simulation::components::TICK_RATE_HZ;
+
+const DEFAULT_RESPAWN_DELAY_SECS: f64 = 5.0;
+
+// Health plugin: damage and heal pipeline, death detection and player respawn
+pub struct HealthPlugin {
+    pub respawn_delay_secs: f64,
+    pub respawn_point: Option<Vec2>,
+}
+
+impl HealthPlugin {
+    // RESPAWN_DELAY_SECS controls the respawn delay; RESPAWN_POINT is "x,y"
+    pub fn from_env() -> Self {
+        let respawn_delay_secs = std::env::var("RESPAWN_DELAY_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_RESPAWN_DELAY_SECS);
+        let respawn_point = std::env::var("RESPAWN_POINT").ok().and_then(|value| {
+            let (x, y) = value.split_once(',')?;
+            Some(Vec2::new(x.trim().parse().ok()?, y.trim().parse().ok()?))
+        });
+
+        Self { respawn_delay_secs, respawn_point }
+    }
+}
+
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HealthConfig {
+                respawn_delay_ticks: (self.respawn_delay_secs * TICK_RATE_HZ).round() as u64,
+                respawn_point: self.respawn_point,
+            })
+            .add_event::<DamageEvent>()
+            .add_event::<HealEvent>()
+            .add_event::<DeathEvent>()
+            .add_systems(FixedUpdate, (
+                health_regen_system,
+                apply_heal_system,
+                apply_damage_system,
+                death_system,
+                player_respawn_system.after(crate::ecs::plugins::loot::systems::loot_drop_system),
+            ).chain()
+                .after(crate::ecs::systems::input_processing_system)
+                .before(crate::ecs::systems::acceleration_friction_system));
+    }
+}
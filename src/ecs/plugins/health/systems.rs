@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+use crate::ecs::components::*;
+use crate::ecs::plugins::collision::components::Collider;
+use crate::ecs::plugins::simulation::components::{SimulationRng, SimulationTick, TICK_RATE_HZ};
+use crate::ecs::plugins::world_map::components::WorldMap;
+use crate::ecs::plugins::world_map::systems::find_open_tile;
+use crate::ecs::plugins::zone::components::{ZoneId, Zones, MAIN_ZONE};
+use super::components::*;
+
+type RespawningPlayerQuery<'w, 's> = Query<'w, 's, (
+    Entity,
+    &'static Player,
+    &'static Dead,
+    &'static mut Health,
+    &'static mut Position,
+    &'static mut Velocity,
+    &'static Collider,
+    &'static ZoneId,
+)>;
+
+// Regeneration is applied once a second rather than every tick, so regenerating
+// entities don't produce a health delta on every tick
+const REGEN_PULSE_TICKS: u64 = TICK_RATE_HZ as u64;
+
+// ============================================================================
+// HEALTH SYSTEMS
+// ============================================================================
+
+pub fn health_regen_system(
+    tick: Res<SimulationTick>,
+    query: Query<(Entity, &Health)>,
+    mut heal_events: EventWriter<HealEvent>,
+) {
+    if !tick.0.is_multiple_of(REGEN_PULSE_TICKS) {
+        return;
+    }
+
+    let pulse_secs = REGEN_PULSE_TICKS as f32 / TICK_RATE_HZ as f32;
+    for (entity, health) in query.iter() {
+        if health.is_dead() || health.current >= health.max || health.regen_per_sec <= 0.0 {
+            continue;
+        }
+        heal_events.send(HealEvent { target: entity, amount: health.regen_per_sec * pulse_secs });
+    }
+}
+
+pub fn apply_heal_system(
+    mut heal_events: EventReader<HealEvent>,
+    mut query: Query<&mut Health>,
+) {
+    for event in heal_events.read() {
+        let Ok(mut health) = query.get_mut(event.target) else {
+            continue;
+        };
+        // The dead can only come back through respawn
+        if health.is_dead() || event.amount <= 0.0 || health.current >= health.max {
+            continue;
+        }
+        health.current = (health.current + event.amount).min(health.max);
+    }
+}
+
+pub fn apply_damage_system(
+    mut damage_events: EventReader<DamageEvent>,
+    mut death_events: EventWriter<DeathEvent>,
+    mut query: Query<&mut Health>,
+) {
+    for event in damage_events.read() {
+        let Ok(mut health) = query.get_mut(event.target) else {
+            continue;
+        };
+        if health.is_dead() || event.amount <= 0.0 {
+            continue;
+        }
+
+        health.current = (health.current - event.amount).max(0.0);
+        if health.is_dead() {
+            death_events.send(DeathEvent { entity: event.target, killer: event.source });
+        }
+    }
+}
+
+pub fn death_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    config: Res<HealthConfig>,
+    mut death_events: EventReader<DeathEvent>,
+    mut players: Query<(&Player, &mut Velocity, &mut DesiredVelocity)>,
+    characters: Query<&Character>,
+) {
+    for event in death_events.read() {
+        let killer = match event.killer {
+            Some(killer) => match (players.get(killer), characters.get(killer)) {
+                (Ok((player, _, _)), _) => format!("player {}", player.id),
+                (_, Ok(character)) => format!("character {}", character.id),
+                _ => "unknown".to_string(),
+            },
+            None => "environment".to_string(),
+        };
+        
+        if let Ok((player, mut velocity, mut desired_velocity)) = players.get_mut(event.entity) {
+            println!("💀 Player {} killed by {}, respawning in {} ticks", player.id, killer, config.respawn_delay_ticks);
+            *velocity = Velocity { x: 0.0, y: 0.0 };
+            *desired_velocity = DesiredVelocity::default();
+            commands.entity(event.entity).insert(Dead { respawn_tick: tick.0 + config.respawn_delay_ticks });
+        } else if let Ok(character) = characters.get(event.entity) {
+            println!("💀 Character {} killed by {}", character.id, killer);
+            commands.entity(event.entity).despawn();
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn player_respawn_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    config: Res<HealthConfig>,
+    game_config: Res<GameConfig>,
+    zones: Res<Zones>,
+    mut rng: ResMut<SimulationRng>,
+    map: Option<Res<WorldMap>>,
+    mut query: RespawningPlayerQuery,
+) {
+    for (entity, player, dead, mut health, mut position, mut velocity, collider, zone) in query.iter_mut() {
+        if tick.0 < dead.respawn_tick {
+            continue;
+        }
+
+        // Players respawn in the zone they died in; the configured point belongs to the main zone
+        let mut point = if *zone == MAIN_ZONE {
+            config.respawn_point.unwrap_or(game_config.world_bounds / 2.0)
+        } else {
+            zones.spawn_point(*zone, &mut **rng).unwrap_or(zones.bounds(*zone, &game_config) / 2.0)
+        };
+        if *zone == MAIN_ZONE
+            && let Some(map) = map.as_deref()
+            && map.overlaps_blocked(point, collider.radius)
+        {
+            point = find_open_tile(map, point, collider.radius).unwrap_or(point);
+        }
+
+        health.current = health.max;
+        *position = Position { x: point.x, y: point.y };
+        *velocity = Velocity { x: 0.0, y: 0.0 };
+        commands.entity(entity).remove::<Dead>();
+
+        println!("✨ Player {} respawned at ({:.0}, {:.0})", player.id, point.x, point.y);
+    }
+}
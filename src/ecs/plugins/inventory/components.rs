@@ -0,0 +1,327 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use crate::ecs::plugins::zone::components::ZoneId;
+
+// ============================================================================
+// ITEM DATA
+// ============================================================================
+
+// What happens when an item is used; using an item consumes one of it
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ItemUse {
+    Heal { amount: f32 },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemDefinition {
+    pub max_stack: u32,
+    #[serde(default, rename = "use")]
+    pub on_use: Option<ItemUse>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StartingItem {
+    pub item: String,
+    pub count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ItemDataFile {
+    #[serde(default)]
+    pub starting_items: Vec<StartingItem>,
+    pub items: HashMap<String, ItemDefinition>,
+}
+
+// ============================================================================
+// INVENTORY COMMANDS
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum InventoryCommand {
+    // Move a stack onto another slot: merges matching stacks, otherwise swaps
+    Move { from: usize, to: usize },
+    // Drop part of a stack, or all of it when no count is given
+    Drop { slot: usize, #[serde(default)] count: Option<u32> },
+    Use { slot: usize },
+    // Pick up a ground item by its network ID
+    Pickup { item: u32 },
+}
+
+impl InventoryCommand {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InventoryCommand::Move { .. } => "move",
+            InventoryCommand::Drop { .. } => "drop",
+            InventoryCommand::Use { .. } => "use",
+            InventoryCommand::Pickup { .. } => "pickup",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InventoryFailure {
+    InvalidSlot,
+    EmptySlot,
+    InvalidCount,
+    NotUsable,
+    Dead,
+    InvalidItem,
+    OutOfRange,
+    InventoryFull,
+}
+
+impl InventoryFailure {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InventoryFailure::InvalidSlot => "invalid_slot",
+            InventoryFailure::EmptySlot => "empty_slot",
+            InventoryFailure::InvalidCount => "invalid_count",
+            InventoryFailure::NotUsable => "not_usable",
+            InventoryFailure::Dead => "dead",
+            InventoryFailure::InvalidItem => "invalid_item",
+            InventoryFailure::OutOfRange => "out_of_range",
+            InventoryFailure::InventoryFull => "inventory_full",
+        }
+    }
+}
+
+// ============================================================================
+// INVENTORY COMPONENTS
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemStack {
+    pub item: String,
+    pub count: u32,
+}
+
+// Fixed number of slots, each holding at most one stack. Every mutation records
+// the slots it touched so only those are sent to the owner.
+#[derive(Component, Debug, Clone)]
+pub struct Inventory {
+    pub slots: Vec<Option<ItemStack>>,
+    pub changed_slots: BTreeSet<usize>,
+}
+
+impl Inventory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            slots: vec![None; capacity],
+            changed_slots: BTreeSet::new(),
+        }
+    }
+
+    // How many of an item fit across partial stacks and empty slots
+    pub fn room_for(&self, item: &str, max_stack: u32) -> u32 {
+        self.slots.iter()
+            .map(|slot| match slot {
+                Some(stack) if stack.item == item => max_stack.saturating_sub(stack.count),
+                Some(_) => 0,
+                None => max_stack,
+            })
+            .sum()
+    }
+
+    // Tops up existing stacks first, then fills empty slots. Returns how many didn't fit.
+    pub fn add(&mut self, item: &str, mut count: u32, max_stack: u32) -> u32 {
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if count == 0 {
+                break;
+            }
+            if let Some(stack) = slot
+                && stack.item == item
+                && stack.count < max_stack
+            {
+                let added = count.min(max_stack - stack.count);
+                stack.count += added;
+                count -= added;
+                self.changed_slots.insert(index);
+            }
+        }
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if count == 0 {
+                break;
+            }
+            if slot.is_none() {
+                let added = count.min(max_stack);
+                *slot = Some(ItemStack { item: item.to_string(), count: added });
+                count -= added;
+                self.changed_slots.insert(index);
+            }
+        }
+        count
+    }
+
+    // Takes up to `count` items out of a slot, clearing it when emptied
+    pub fn take(&mut self, slot: usize, count: u32) -> Option<ItemStack> {
+        let stack = self.slots.get_mut(slot)?.as_mut()?;
+        let taken = count.min(stack.count);
+        stack.count -= taken;
+        let item = stack.item.clone();
+        if stack.count == 0 {
+            self.slots[slot] = None;
+        }
+        self.changed_slots.insert(slot);
+        Some(ItemStack { item, count: taken })
+    }
+
+    // Takes part of a stack to drop, or all of it when no count is given. Partial counts
+    // must be between 1 and the stack size.
+    pub fn take_to_drop(&mut self, slot: usize, count: Option<u32>) -> Result<ItemStack, InventoryFailure> {
+        let stack = self.slots.get(slot).ok_or(InventoryFailure::InvalidSlot)?;
+        let stack = stack.as_ref().ok_or(InventoryFailure::EmptySlot)?;
+        let count = count.unwrap_or(stack.count);
+        if count == 0 || count > stack.count {
+            return Err(InventoryFailure::InvalidCount);
+        }
+        self.take(slot, count).ok_or(InventoryFailure::EmptySlot)
+    }
+
+    // Merges `from` into `to` when they hold the same item, otherwise swaps them
+    pub fn move_stack(&mut self, from: usize, to: usize, max_stack: u32) {
+        match (&self.slots[from], &self.slots[to]) {
+            (Some(source), Some(target)) if source.item == target.item => {
+                let moved = source.count.min(max_stack.saturating_sub(target.count));
+                if let Some(target) = self.slots[to].as_mut() {
+                    target.count += moved;
+                }
+                self.take(from, moved);
+            }
+            _ => self.slots.swap(from, to),
+        }
+        self.changed_slots.insert(from);
+        self.changed_slots.insert(to);
+    }
+}
+
+// Slots a returning player was saved with, set by snapshot restore and profile loading
+// (the profile goes last, so it wins) and used instead of the starting items
+#[derive(Component, Debug, Clone)]
+pub struct SavedInventory(pub Vec<Option<ItemStack>>);
+
+// ============================================================================
+// INVENTORY EVENTS
+// ============================================================================
+
+// Items a player dropped at `position`, to be placed on the ground
+#[derive(Event, Debug, Clone)]
+pub struct ItemDroppedEvent {
+    pub stack: ItemStack,
+    pub position: Vec2,
+    pub zone: ZoneId,
+}
+
+// ============================================================================
+// INVENTORY RESOURCES
+// ============================================================================
+
+#[derive(Resource, Default)]
+pub struct ItemDefinitions {
+    pub items: HashMap<String, ItemDefinition>,
+    pub starting_items: Vec<StartingItem>,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct InventoryConfig {
+    pub capacity: usize,
+}
+
+// ============================================================================
+// INVENTORY MESSAGE KEYS
+// ============================================================================
+
+pub const CAPACITY_KEY: &str = "cap";
+pub const SLOTS_KEY: &str = "s";
+pub const OPERATION_KEY: &str = "op";
+pub const REASON_KEY: &str = "r";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stack(item: &str, count: u32) -> Option<ItemStack> {
+        Some(ItemStack { item: item.to_string(), count })
+    }
+
+    #[test]
+    fn adding_tops_up_stacks_before_using_empty_slots() {
+        let mut inventory = Inventory::new(4);
+        inventory.slots[1] = stack("bread", 18);
+        inventory.slots[2] = stack("potion", 1);
+        assert_eq!(inventory.add("bread", 5, 20), 0);
+        assert_eq!(inventory.slots, vec![stack("bread", 3), stack("bread", 20), stack("potion", 1), None]);
+        assert_eq!(inventory.changed_slots.iter().copied().collect::<Vec<_>>(), vec![0, 1]);
+    }
+
+    #[test]
+    fn large_adds_spill_into_as_many_slots_as_needed() {
+        let mut inventory = Inventory::new(4);
+        assert_eq!(inventory.room_for("bread", 20), 80);
+        assert_eq!(inventory.add("bread", 45, 20), 0);
+        assert_eq!(inventory.slots, vec![stack("bread", 20), stack("bread", 20), stack("bread", 5), None]);
+        assert_eq!(inventory.room_for("bread", 20), 35);
+    }
+
+    #[test]
+    fn a_full_inventory_returns_what_did_not_fit() {
+        let mut inventory = Inventory::new(2);
+        inventory.slots[0] = stack("potion", 10);
+        inventory.slots[1] = stack("bread", 19);
+        assert_eq!(inventory.room_for("bread", 20), 1);
+        assert_eq!(inventory.room_for("sword", 1), 0);
+        assert_eq!(inventory.add("bread", 4, 20), 3);
+        assert_eq!(inventory.add("sword", 1, 1), 1);
+        assert_eq!(inventory.slots, vec![stack("potion", 10), stack("bread", 20)]);
+    }
+
+    #[test]
+    fn taking_part_of_a_stack_leaves_the_rest() {
+        let mut inventory = Inventory::new(2);
+        inventory.slots[0] = stack("bread", 5);
+        assert_eq!(inventory.take(0, 2), stack("bread", 2));
+        assert_eq!(inventory.slots[0], stack("bread", 3));
+        assert_eq!(inventory.take(0, 9), stack("bread", 3));
+        assert_eq!(inventory.slots[0], None);
+        assert_eq!(inventory.take(0, 1), None);
+        assert_eq!(inventory.take(7, 1), None);
+    }
+
+    #[test]
+    fn moving_onto_the_same_item_merges_up_to_the_stack_limit() {
+        let mut inventory = Inventory::new(3);
+        inventory.slots[0] = stack("bread", 8);
+        inventory.slots[1] = stack("bread", 15);
+        inventory.move_stack(0, 1, 20);
+        assert_eq!(inventory.slots[..2], [stack("bread", 3), stack("bread", 20)]);
+        inventory.slots[2] = stack("bread", 4);
+        inventory.move_stack(0, 2, 20);
+        assert_eq!(inventory.slots, vec![None, stack("bread", 20), stack("bread", 7)]);
+    }
+
+    #[test]
+    fn moving_onto_another_item_or_an_empty_slot_swaps() {
+        let mut inventory = Inventory::new(3);
+        inventory.slots[0] = stack("bread", 8);
+        inventory.slots[1] = stack("potion", 2);
+        inventory.move_stack(0, 1, 20);
+        assert_eq!(inventory.slots, vec![stack("potion", 2), stack("bread", 8), None]);
+        inventory.move_stack(1, 2, 20);
+        assert_eq!(inventory.slots, vec![stack("potion", 2), None, stack("bread", 8)]);
+        assert_eq!(inventory.changed_slots.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn drops_must_take_between_one_item_and_the_whole_stack() {
+        let mut inventory = Inventory::new(2);
+        inventory.slots[0] = stack("bread", 5);
+        assert_eq!(inventory.take_to_drop(0, Some(0)), Err(InventoryFailure::InvalidCount));
+        assert_eq!(inventory.take_to_drop(0, Some(6)), Err(InventoryFailure::InvalidCount));
+        assert_eq!(inventory.slots[0], stack("bread", 5));
+        assert_eq!(inventory.take_to_drop(0, Some(2)), Ok(ItemStack { item: "bread".to_string(), count: 2 }));
+        assert_eq!(inventory.take_to_drop(0, None), Ok(ItemStack { item: "bread".to_string(), count: 3 }));
+        assert_eq!(inventory.take_to_drop(0, None), Err(InventoryFailure::EmptySlot));
+        assert_eq!(inventory.take_to_drop(2, Some(1)), Err(InventoryFailure::InvalidSlot));
+    }
+}
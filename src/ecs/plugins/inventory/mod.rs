@@ -0,0 +1,59 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::{InventoryConfig, ItemDataFile, ItemDefinitions, ItemDroppedEvent};
+use systems::{attach_inventory_system, inventory_command_system, inventory_sync_system};
+
+const DEFAULT_ITEM_DATA_PATH: &str = "data/items/items.json";
+const DEFAULT_INVENTORY_SLOTS: usize = 20;
+
+// Inventory plugin: data-driven items in slot-based player inventories, changed
+// only by validated commands and synced privately to their owner
+pub struct InventoryPlugin {
+    pub data_path: String,
+    pub capacity: usize,
+}
+
+impl InventoryPlugin {
+    // ITEM_DATA overrides the default item file; INVENTORY_SLOTS sets the slot count
+    pub fn from_env() -> Self {
+        let data_path = std::env::var("ITEM_DATA").unwrap_or_else(|_| DEFAULT_ITEM_DATA_PATH.to_string());
+        let capacity = std::env::var("INVENTORY_SLOTS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_INVENTORY_SLOTS);
+
+        Self { data_path, capacity }
+    }
+}
+
+impl Plugin for InventoryPlugin {
+    fn build(&self, app: &mut App) {
+        let data = std::fs::read_to_string(&self.data_path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| serde_json::from_str::<ItemDataFile>(&data).map_err(|e| e.to_string()));
+
+        let definitions = match data {
+            Ok(data) => {
+                println!("🎒 Loaded {} items from {}", data.items.len(), self.data_path);
+                ItemDefinitions { items: data.items, starting_items: data.starting_items }
+            }
+            Err(e) => {
+                println!("❌ Failed to load item data {}: {}", self.data_path, e);
+                ItemDefinitions::default()
+            }
+        };
+
+        app.insert_resource(definitions)
+            .insert_resource(InventoryConfig { capacity: self.capacity })
+            .add_event::<ItemDroppedEvent>()
+            .add_systems(FixedUpdate, (
+                attach_inventory_system.after(crate::ecs::systems::player_spawn_system),
+                inventory_command_system
+                    .after(crate::ecs::systems::input_processing_system)
+                    .before(crate::ecs::plugins::health::systems::apply_heal_system),
+                inventory_sync_system.after(inventory_command_system),
+            ));
+    }
+}
@@ -0,0 +1,198 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::ecs::components::*;
+use crate::ecs::plugins::health::components::{Dead, HealEvent};
+use crate::ecs::plugins::network::components::{EntityUpdate, NetworkId, NetworkMessage, NetworkUpdates, INVENTORY_TYPE};
+use crate::ecs::plugins::zone::components::ZoneId;
+use super::components::*;
+
+type InventoryOwnerQuery<'w, 's> = Query<'w, 's, (
+    &'static Player,
+    &'static NetworkId,
+    &'static Position,
+    &'static ZoneId,
+    &'static mut Inventory,
+    Has<Dead>,
+)>;
+
+type NewInventoryOwnerQuery<'w, 's> = Query<'w, 's, (
+    Entity,
+    &'static Player,
+    &'static NetworkId,
+    Option<&'static Account>,
+    Option<&'static SavedInventory>,
+), Added<Player>>;
+
+// ============================================================================
+// INVENTORY MESSAGES
+// ============================================================================
+
+// Inventory messages go only to the owner, keyed by the owner's network ID
+fn inventory_message(owner: u32, fields: Vec<(&str, serde_json::Value)>) -> NetworkMessage {
+    let mut components = HashMap::new();
+    for (key, value) in fields {
+        components.insert(key.to_string(), value);
+    }
+    NetworkMessage {
+        message_type: INVENTORY_TYPE.to_string(),
+        entity_updates: vec![EntityUpdate { network_id: owner, components }],
+        tick: None,
+    }
+}
+
+// Slots are sent as [index, item, count], with a null item for empty slots
+fn slots_json(inventory: &Inventory, indices: impl Iterator<Item = usize>) -> serde_json::Value {
+    indices
+        .filter_map(|index| inventory.slots.get(index).map(|slot| (index, slot)))
+        .map(|(index, slot)| match slot {
+            Some(stack) => serde_json::json!([index, stack.item, stack.count]),
+            None => serde_json::json!([index, null, 0]),
+        })
+        .collect()
+}
+
+pub fn send_failure(network_updates: &mut NetworkUpdates, player: &Player, owner: u32, command: &InventoryCommand, failure: InventoryFailure) {
+    let message = inventory_message(owner, vec![
+        (OPERATION_KEY, serde_json::Value::String(command.as_str().to_string())),
+        (REASON_KEY, serde_json::Value::String(failure.as_str().to_string())),
+    ]);
+    network_updates.player_messages.entry(player.id).or_default().push(message);
+}
+
+// ============================================================================
+// INVENTORY SYSTEMS
+// ============================================================================
+
+// Gives new players their saved inventory, or the starting items for a new account,
+// and sends them all of it. Guests start empty: nothing ties a reconnecting guest to
+// what they dropped, so a fresh set each time could be piled up on the ground.
+pub fn attach_inventory_system(
+    mut commands: Commands,
+    config: Res<InventoryConfig>,
+    definitions: Res<ItemDefinitions>,
+    mut network_updates: ResMut<NetworkUpdates>,
+    query: NewInventoryOwnerQuery,
+) {
+    for (entity, player, network_id, account, saved) in query.iter() {
+        let mut inventory = Inventory::new(config.capacity);
+        match (saved, account) {
+            (Some(saved), _) => {
+                inventory.slots = saved.0.clone();
+                if inventory.slots.iter().skip(config.capacity).any(Option::is_some) {
+                    println!("❌ Player {} was saved with more than {} slots; the rest are lost", player.id, config.capacity);
+                }
+                inventory.slots.resize(config.capacity, None);
+                commands.entity(entity).remove::<SavedInventory>();
+            }
+            (None, Some(_)) => {
+                for starting_item in &definitions.starting_items {
+                    if let Some(definition) = definitions.items.get(&starting_item.item) {
+                        inventory.add(&starting_item.item, starting_item.count, definition.max_stack);
+                    }
+                }
+                inventory.changed_slots.clear();
+            }
+            (None, None) => {}
+        }
+
+        let message = inventory_message(network_id.0, vec![
+            (CAPACITY_KEY, serde_json::Value::from(config.capacity)),
+            (SLOTS_KEY, slots_json(&inventory, 0..config.capacity)),
+        ]);
+        network_updates.player_messages.entry(player.id).or_default().push(message);
+        commands.entity(entity).insert(inventory);
+    }
+}
+
+// Validates and applies move, drop and use commands against the server's inventory
+pub fn inventory_command_system(
+    definitions: Res<ItemDefinitions>,
+    player_registry: Res<PlayerRegistry>,
+    mut network_updates: ResMut<NetworkUpdates>,
+    mut input_events: EventReader<InputCommandEvent>,
+    mut heal_events: EventWriter<HealEvent>,
+    mut drop_events: EventWriter<ItemDroppedEvent>,
+    mut query: InventoryOwnerQuery,
+) {
+    for event in input_events.read() {
+        let InputCommand::Inventory(command) = &event.command else {
+            continue;
+        };
+        let Some(entity) = player_registry.get_player_entity(event.player_id) else {
+            continue;
+        };
+        let Ok((player, network_id, position, zone, mut inventory, dead)) = query.get_mut(entity) else {
+            continue;
+        };
+
+        let slot = match command {
+            InventoryCommand::Move { from, to } => {
+                if *to >= inventory.slots.len() {
+                    send_failure(&mut network_updates, player, network_id.0, command, InventoryFailure::InvalidSlot);
+                    continue;
+                }
+                *from
+            }
+            InventoryCommand::Drop { slot, .. } | InventoryCommand::Use { slot } => *slot,
+            // Ground items are handled by the loot plugin
+            InventoryCommand::Pickup { .. } => continue,
+        };
+        let Some(stack) = inventory.slots.get(slot) else {
+            send_failure(&mut network_updates, player, network_id.0, command, InventoryFailure::InvalidSlot);
+            continue;
+        };
+        let Some(stack) = stack.clone() else {
+            send_failure(&mut network_updates, player, network_id.0, command, InventoryFailure::EmptySlot);
+            continue;
+        };
+        let definition = definitions.items.get(&stack.item);
+
+        match command {
+            InventoryCommand::Move { from, to } => {
+                if from != to {
+                    inventory.move_stack(*from, *to, definition.map_or(1, |definition| definition.max_stack));
+                }
+            }
+            InventoryCommand::Drop { count, .. } => match inventory.take_to_drop(slot, *count) {
+                Ok(dropped) => {
+                    drop_events.send(ItemDroppedEvent { stack: dropped, position: Vec2::new(position.x, position.y), zone: *zone });
+                }
+                Err(failure) => send_failure(&mut network_updates, player, network_id.0, command, failure),
+            },
+            InventoryCommand::Use { .. } => {
+                if dead {
+                    send_failure(&mut network_updates, player, network_id.0, command, InventoryFailure::Dead);
+                    continue;
+                }
+                let Some(on_use) = definition.and_then(|definition| definition.on_use) else {
+                    send_failure(&mut network_updates, player, network_id.0, command, InventoryFailure::NotUsable);
+                    continue;
+                };
+                inventory.take(slot, 1);
+                match on_use {
+                    ItemUse::Heal { amount } => {
+                        heal_events.send(HealEvent { target: entity, amount });
+                    }
+                }
+            }
+            InventoryCommand::Pickup { .. } => {}
+        }
+    }
+}
+
+// Sends each owner the slots that changed this tick, on their private channel
+pub fn inventory_sync_system(
+    mut network_updates: ResMut<NetworkUpdates>,
+    mut query: Query<(&Player, &NetworkId, &mut Inventory), Changed<Inventory>>,
+) {
+    for (player, network_id, mut inventory) in query.iter_mut() {
+        if inventory.changed_slots.is_empty() {
+            continue;
+        }
+        let message = inventory_message(network_id.0, vec![
+            (SLOTS_KEY, slots_json(&inventory, inventory.changed_slots.iter().copied())),
+        ]);
+        network_updates.player_messages.entry(player.id).or_default().push(message);
+        inventory.changed_slots.clear();
+    }
+}
@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use crate::ecs::components::Position;
+
+// ============================================================================
+// LAG COMPENSATION COMPONENTS
+// ============================================================================
+
+// Positions at the end of recent ticks, oldest first
+#[derive(Component, Debug, Clone, Default)]
+pub struct PositionHistory {
+    pub samples: VecDeque<(u64, Vec2)>,
+}
+
+impl PositionHistory {
+    pub fn record(&mut self, tick: u64, position: Vec2, capacity: usize) {
+        self.samples.push_back((tick, position));
+        while self.samples.len() > capacity {
+            self.samples.pop_front();
+        }
+    }
+
+    // Position as of the end of `tick`, or the oldest sample if that is no longer kept
+    pub fn at(&self, tick: u64) -> Option<Vec2> {
+        self.samples.iter()
+            .rev()
+            .find(|(sample_tick, _)| *sample_tick <= tick)
+            .or(self.samples.front())
+            .map(|(_, position)| *position)
+    }
+}
+
+// Latest tick the client has acknowledged receiving
+#[derive(Component, Debug, Clone, Copy)]
+pub struct AckedTick(pub u64);
+
+// ============================================================================
+// LAG COMPENSATION RESOURCES
+// ============================================================================
+
+#[derive(Resource, Debug, Clone)]
+pub struct LagCompensationConfig {
+    // Hit checks never look further back than this, however stale a client's ack is
+    pub max_rewind_ticks: u64,
+}
+
+impl LagCompensationConfig {
+    // The tick a player was looking at when it acted, clamped to the rewind window.
+    // None means no rewind (the player never acked, or is fully caught up).
+    pub fn view_tick(&self, now: u64, acked: Option<&AckedTick>) -> Option<u64> {
+        let acked = acked?.0;
+        (acked < now).then(|| acked.max(now.saturating_sub(self.max_rewind_ticks)))
+    }
+}
+
+// Where an entity was at the end of `tick`, or where it is now when not rewinding
+pub fn rewound_position(history: Option<&PositionHistory>, position: &Position, tick: Option<u64>) -> Vec2 {
+    tick.zip(history)
+        .and_then(|(tick, history)| history.at(tick))
+        .unwrap_or(Vec2::new(position.x, position.y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(ticks: std::ops::RangeInclusive<u64>, capacity: usize) -> PositionHistory {
+        let mut history = PositionHistory::default();
+        for tick in ticks {
+            history.record(tick, Vec2::new(tick as f32, 0.0), capacity);
+        }
+        history
+    }
+
+    #[test]
+    fn returns_the_sample_recorded_at_a_tick() {
+        let history = history(10..=14, 8);
+        assert_eq!(history.at(12), Some(Vec2::new(12.0, 0.0)));
+        assert_eq!(history.at(10), Some(Vec2::new(10.0, 0.0)));
+    }
+
+    #[test]
+    fn ticks_older_than_the_buffer_fall_back_to_the_oldest_sample() {
+        let history = history(10..=14, 8);
+        assert_eq!(history.at(3), Some(Vec2::new(10.0, 0.0)));
+    }
+
+    #[test]
+    fn future_ticks_use_the_latest_sample() {
+        let history = history(10..=14, 8);
+        assert_eq!(history.at(20), Some(Vec2::new(14.0, 0.0)));
+    }
+
+    #[test]
+    fn only_the_most_recent_samples_are_kept() {
+        let history = history(1..=20, 5);
+        assert_eq!(history.samples.len(), 5);
+        assert_eq!(history.at(18), Some(Vec2::new(18.0, 0.0)));
+        assert_eq!(history.at(15), Some(Vec2::new(16.0, 0.0)));
+        assert_eq!(history.at(2), Some(Vec2::new(16.0, 0.0)));
+    }
+
+    #[test]
+    fn an_empty_history_has_no_position() {
+        assert_eq!(PositionHistory::default().at(5), None);
+    }
+}
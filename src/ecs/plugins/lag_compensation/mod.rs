@@ -0,0 +1,39 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::LagCompensationConfig;
+use systems::{attach_position_history_system, record_acks_system, record_position_history_system};
+use crate::ecs::plugins::simulation::components::TICK_RATE_HZ;
+
+const DEFAULT_MAX_REWIND_MS: f64 = 300.0;
+
+// Lag compensation plugin: keeps recent positions of networked entities so hit checks
+// can rewind to the tick the acting client last acknowledged
+pub struct LagCompensationPlugin {
+    pub max_rewind_ms: f64,
+}
+
+impl LagCompensationPlugin {
+    // LAG_COMP_MAX_REWIND_MS caps how far back hit checks may rewind
+    pub fn from_env() -> Self {
+        let max_rewind_ms = std::env::var("LAG_COMP_MAX_REWIND_MS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_MAX_REWIND_MS);
+
+        Self { max_rewind_ms }
+    }
+}
+
+impl Plugin for LagCompensationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(LagCompensationConfig {
+                max_rewind_ticks: (self.max_rewind_ms / 1000.0 * TICK_RATE_HZ).round() as u64,
+            })
+            .add_systems(FixedUpdate, record_acks_system
+                .after(crate::ecs::systems::input_processing_system)
+                .before(crate::ecs::plugins::ability::systems::ability_cast_request_system))
+            .add_systems(FixedLast, (attach_position_history_system, record_position_history_system).chain());
+    }
+}
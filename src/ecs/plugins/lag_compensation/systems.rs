@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::ecs::components::*;
+use crate::ecs::plugins::network::components::{NetworkExtrapolated, NetworkId};
+use crate::ecs::plugins::simulation::components::SimulationTick;
+use super::components::*;
+
+// ============================================================================
+// LAG COMPENSATION SYSTEMS
+// ============================================================================
+
+// Acks only move forward and can never claim a tick that hasn't happened yet
+pub fn record_acks_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    player_registry: Res<PlayerRegistry>,
+    mut input_events: EventReader<InputCommandEvent>,
+    acked: Query<&AckedTick>,
+) {
+    let mut latest: HashMap<u32, u64> = HashMap::new();
+    for event in input_events.read() {
+        if let InputCommand::Ack { tick: acked_tick } = event.command {
+            let entry = latest.entry(event.player_id).or_default();
+            *entry = (*entry).max(acked_tick.min(tick.0));
+        }
+    }
+
+    for (player_id, acked_tick) in latest {
+        let Some(entity) = player_registry.get_player_entity(player_id) else {
+            continue;
+        };
+        let previous = acked.get(entity).map(|acked| acked.0).unwrap_or(0);
+        commands.entity(entity).insert(AckedTick(acked_tick.max(previous)));
+    }
+}
+
+// Extrapolated entities (projectiles) are never hit, so they keep no history
+pub fn attach_position_history_system(
+    mut commands: Commands,
+    query: Query<Entity, (Added<NetworkId>, Without<NetworkExtrapolated>)>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).insert(PositionHistory::default());
+    }
+}
+
+// Runs at the end of every tick, after all movement
+pub fn record_position_history_system(
+    tick: Res<SimulationTick>,
+    config: Res<LagCompensationConfig>,
+    mut query: Query<(&Position, &mut PositionHistory)>,
+) {
+    let capacity = config.max_rewind_ticks as usize + 1;
+    for (position, mut history) in query.iter_mut() {
+        history.record(tick.0, Vec2::new(position.x, position.y), capacity);
+    }
+}
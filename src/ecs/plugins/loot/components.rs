@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use crate::ecs::components::Position;
+use crate::ecs::plugins::inventory::components::ItemStack;
+use crate::ecs::plugins::network::components::{NetworkEntityKind, NetworkedEntityBundle};
+use crate::ecs::plugins::zone::components::ZoneId;
+
+// ============================================================================
+// LOOT DATA
+// ============================================================================
+
+// One roll of a loot table: drops min..=max of the item with the given chance
+#[derive(Debug, Clone, Deserialize)]
+pub struct LootEntry {
+    pub item: String,
+    #[serde(default = "default_chance")]
+    pub chance: f32,
+    #[serde(default = "default_count")]
+    pub min: u32,
+    #[serde(default = "default_count")]
+    pub max: u32,
+}
+
+fn default_chance() -> f32 {
+    1.0
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LootTableFile {
+    pub loot_tables: HashMap<String, Vec<LootEntry>>,
+}
+
+// ============================================================================
+// LOOT COMPONENTS
+// ============================================================================
+
+// Loot table rolled when this character dies
+#[derive(Component, Debug, Clone)]
+pub struct DropsLoot {
+    pub table: String,
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct GroundItem {
+    pub stack: ItemStack,
+    pub expires_tick: u64,
+}
+
+// Static and collision-free; the item stack never changes, so it lives in the snapshot
+#[derive(Bundle)]
+pub struct GroundItemBundle {
+    pub ground_item: GroundItem,
+    pub position: Position,
+    pub zone: ZoneId,
+    pub networked: NetworkedEntityBundle,
+}
+
+impl GroundItemBundle {
+    pub fn new(ground_item: GroundItem, position: Vec2, zone: ZoneId, network_id: u32) -> Self {
+        let mut networked = NetworkedEntityBundle::new(network_id, NetworkEntityKind::Item);
+        networked.snapshot.components.insert(
+            ITEM_KEY.to_string(),
+            serde_json::json!([ground_item.stack.item, ground_item.stack.count]),
+        );
+
+        Self {
+            ground_item,
+            position: Position { x: position.x, y: position.y },
+            zone,
+            networked,
+        }
+    }
+}
+
+// ============================================================================
+// LOOT RESOURCES
+// ============================================================================
+
+#[derive(Resource, Default)]
+pub struct LootTables {
+    pub tables: HashMap<String, Vec<LootEntry>>,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct LootConfig {
+    pub pickup_range: f32,
+    pub despawn_ticks: u64,
+}
+
+// ============================================================================
+// LOOT MESSAGE KEYS
+// ============================================================================
+
+pub const ITEM_KEY: &str = "it";
+pub const REASON_KEY: &str = "r";
+pub const PICKED_BY_KEY: &str = "pl";
+
+pub const REASON_PICKED_UP: &str = "picked_up";
+pub const REASON_EXPIRED: &str = "expired";
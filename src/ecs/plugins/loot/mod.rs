@@ -0,0 +1,72 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::{LootConfig, LootTableFile, LootTables};
+use systems::{ground_item_expiry_system, item_pickup_system, loot_drop_system, spawn_dropped_items_system};
+use crate::ecs::plugins::simulation::components::TICK_RATE_HZ;
+
+const DEFAULT_LOOT_DATA_PATH: &str = "data/items/loot_tables.json";
+const DEFAULT_PICKUP_RANGE: f32 = 48.0;
+const DEFAULT_GROUND_ITEM_DESPAWN_SECS: f64 = 120.0;
+
+// Loot plugin: networked ground items from player drops and character loot tables,
+// picked up into inventories and despawned after a while
+pub struct LootPlugin {
+    pub data_path: String,
+    pub pickup_range: f32,
+    pub despawn_secs: f64,
+}
+
+impl LootPlugin {
+    // LOOT_DATA overrides the loot table file; ITEM_PICKUP_RANGE and
+    // GROUND_ITEM_DESPAWN_SECS control pickups and how long items stay on the ground
+    pub fn from_env() -> Self {
+        let data_path = std::env::var("LOOT_DATA").unwrap_or_else(|_| DEFAULT_LOOT_DATA_PATH.to_string());
+        let pickup_range = std::env::var("ITEM_PICKUP_RANGE")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_PICKUP_RANGE);
+        let despawn_secs = std::env::var("GROUND_ITEM_DESPAWN_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_GROUND_ITEM_DESPAWN_SECS);
+
+        Self { data_path, pickup_range, despawn_secs }
+    }
+}
+
+impl Plugin for LootPlugin {
+    fn build(&self, app: &mut App) {
+        let data = std::fs::read_to_string(&self.data_path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| serde_json::from_str::<LootTableFile>(&data).map_err(|e| e.to_string()));
+
+        let tables = match data {
+            Ok(data) => {
+                println!("💰 Loaded {} loot tables from {}", data.loot_tables.len(), self.data_path);
+                data.loot_tables
+            }
+            Err(e) => {
+                println!("❌ Failed to load loot data {}: {}", self.data_path, e);
+                Default::default()
+            }
+        };
+
+        app.insert_resource(LootTables { tables })
+            .insert_resource(LootConfig {
+                pickup_range: self.pickup_range,
+                despawn_ticks: (self.despawn_secs * TICK_RATE_HZ).round() as u64,
+            })
+            .add_systems(FixedUpdate, (
+                loot_drop_system
+                    .after(crate::ecs::plugins::ai::systems::ai_think_system)
+                    .after(crate::ecs::plugins::health::systems::apply_damage_system)
+                    .before(crate::ecs::plugins::health::systems::death_system),
+                (spawn_dropped_items_system, ground_item_expiry_system, item_pickup_system).chain()
+                    .after(crate::ecs::plugins::inventory::systems::inventory_command_system)
+                    .before(crate::ecs::plugins::inventory::systems::inventory_sync_system)
+                    .before(crate::ecs::plugins::health::systems::apply_heal_system),
+            ));
+    }
+}
@@ -0,0 +1,192 @@
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+use crate::ecs::components::*;
+use crate::ecs::plugins::health::components::{DeathEvent, Dead};
+use crate::ecs::plugins::inventory::components::{Inventory, InventoryCommand, InventoryFailure, ItemDefinitions, ItemDroppedEvent, ItemStack};
+use crate::ecs::plugins::inventory::systems::send_failure;
+use crate::ecs::plugins::network::components::{
+    EntityUpdate, NetworkId, NetworkIdAllocator, NetworkMessage, NetworkUpdates, ViewRangeTracker, ITEM_END_TYPE,
+};
+use crate::ecs::plugins::simulation::components::{SimulationRng, SimulationTick};
+use crate::ecs::plugins::zone::components::ZoneId;
+use super::components::*;
+
+type LooterQuery<'w, 's> = Query<'w, 's, (
+    &'static Player,
+    &'static NetworkId,
+    &'static Position,
+    &'static ZoneId,
+    &'static mut Inventory,
+    Has<Dead>,
+)>;
+
+// Loot from one death is scattered this far around the body
+const LOOT_SCATTER: f32 = 12.0;
+
+// ============================================================================
+// LOOT HELPERS
+// ============================================================================
+
+fn spawn_ground_item(
+    commands: &mut Commands,
+    allocator: &mut NetworkIdAllocator,
+    tick: u64,
+    config: &LootConfig,
+    stack: ItemStack,
+    position: Vec2,
+    zone: ZoneId,
+) {
+    let ground_item = GroundItem { stack, expires_tick: tick + config.despawn_ticks };
+    commands.spawn(GroundItemBundle::new(ground_item, position, zone, allocator.allocate()));
+}
+
+// Tells everyone who can see a ground item that it is gone
+fn send_item_end(network_updates: &mut NetworkUpdates, network_id: u32, view_tracker: &ViewRangeTracker, fields: Vec<(&str, serde_json::Value)>) {
+    let mut components = HashMap::new();
+    for (key, value) in fields {
+        components.insert(key.to_string(), value);
+    }
+    let message = NetworkMessage {
+        message_type: ITEM_END_TYPE.to_string(),
+        entity_updates: vec![EntityUpdate { network_id, components }],
+        tick: None,
+    };
+    for player_id in &view_tracker.players_in_view {
+        network_updates.player_messages.entry(*player_id).or_default().push(message.clone());
+    }
+}
+
+// ============================================================================
+// LOOT SYSTEMS
+// ============================================================================
+
+// Rolls the loot table of characters that just died, before death_system despawns them
+#[allow(clippy::too_many_arguments)]
+pub fn loot_drop_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    config: Res<LootConfig>,
+    tables: Res<LootTables>,
+    mut rng: ResMut<SimulationRng>,
+    mut allocator: ResMut<NetworkIdAllocator>,
+    mut death_events: EventReader<DeathEvent>,
+    query: Query<(&DropsLoot, &Position, &ZoneId)>,
+) {
+    for event in death_events.read() {
+        let Ok((drops_loot, position, zone)) = query.get(event.entity) else {
+            continue;
+        };
+        let Some(table) = tables.tables.get(&drops_loot.table) else {
+            continue;
+        };
+
+        for entry in table {
+            if !rng.gen_bool(entry.chance.clamp(0.0, 1.0) as f64) {
+                continue;
+            }
+            let count = rng.gen_range(entry.min..=entry.max.max(entry.min));
+            if count == 0 {
+                continue;
+            }
+            let offset = Vec2::new(rng.gen_range(-LOOT_SCATTER..=LOOT_SCATTER), rng.gen_range(-LOOT_SCATTER..=LOOT_SCATTER));
+            let stack = ItemStack { item: entry.item.clone(), count };
+            spawn_ground_item(&mut commands, &mut allocator, tick.0, &config, stack, Vec2::new(position.x, position.y) + offset, *zone);
+        }
+    }
+}
+
+pub fn spawn_dropped_items_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    config: Res<LootConfig>,
+    mut allocator: ResMut<NetworkIdAllocator>,
+    mut drop_events: EventReader<ItemDroppedEvent>,
+) {
+    for event in drop_events.read() {
+        spawn_ground_item(&mut commands, &mut allocator, tick.0, &config, event.stack.clone(), event.position, event.zone);
+    }
+}
+
+// Picks up a ground item only if the player is in range and the whole stack fits,
+// so an item is either fully in one inventory or still on the ground. Runs after
+// expiry and ignores items expiring this tick, so an item either expires or is
+// picked up, never both.
+#[allow(clippy::too_many_arguments)]
+pub fn item_pickup_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    config: Res<LootConfig>,
+    definitions: Res<ItemDefinitions>,
+    player_registry: Res<PlayerRegistry>,
+    mut network_updates: ResMut<NetworkUpdates>,
+    mut input_events: EventReader<InputCommandEvent>,
+    mut players: LooterQuery,
+    items: Query<(Entity, &NetworkId, &GroundItem, &Position, &ZoneId, &ViewRangeTracker)>,
+) {
+    // Despawns are deferred, so two pickups of one item in a tick must be caught here
+    let mut taken = HashSet::new();
+
+    for event in input_events.read() {
+        let InputCommand::Inventory(command @ InventoryCommand::Pickup { item }) = &event.command else {
+            continue;
+        };
+        let Some(player_entity) = player_registry.get_player_entity(event.player_id) else {
+            continue;
+        };
+        let Ok((player, network_id, position, zone, mut inventory, dead)) = players.get_mut(player_entity) else {
+            continue;
+        };
+
+        if dead {
+            send_failure(&mut network_updates, player, network_id.0, command, InventoryFailure::Dead);
+            continue;
+        }
+        let found = items.iter()
+            .find(|(entity, item_network_id, ground_item, _, _, _)| {
+                item_network_id.0 == *item && tick.0 < ground_item.expires_tick && !taken.contains(entity)
+            });
+        let Some((item_entity, item_network_id, ground_item, item_position, item_zone, view_tracker)) = found else {
+            send_failure(&mut network_updates, player, network_id.0, command, InventoryFailure::InvalidItem);
+            continue;
+        };
+        let distance = Vec2::new(position.x, position.y).distance(Vec2::new(item_position.x, item_position.y));
+        if item_zone != zone || distance > config.pickup_range {
+            send_failure(&mut network_updates, player, network_id.0, command, InventoryFailure::OutOfRange);
+            continue;
+        }
+        let stack = &ground_item.stack;
+        let max_stack = definitions.items.get(&stack.item).map_or(1, |definition| definition.max_stack);
+        if inventory.room_for(&stack.item, max_stack) < stack.count {
+            send_failure(&mut network_updates, player, network_id.0, command, InventoryFailure::InventoryFull);
+            continue;
+        }
+
+        inventory.add(&stack.item, stack.count, max_stack);
+        taken.insert(item_entity);
+        commands.entity(item_entity).despawn();
+        println!("🎁 Player {} picked up {} x{}", player.id, stack.item, stack.count);
+
+        send_item_end(&mut network_updates, item_network_id.0, view_tracker, vec![
+            (REASON_KEY, serde_json::Value::String(REASON_PICKED_UP.to_string())),
+            (PICKED_BY_KEY, serde_json::Value::from(player.id)),
+        ]);
+    }
+}
+
+pub fn ground_item_expiry_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    mut network_updates: ResMut<NetworkUpdates>,
+    items: Query<(Entity, &NetworkId, &GroundItem, &ViewRangeTracker)>,
+) {
+    for (entity, network_id, ground_item, view_tracker) in items.iter() {
+        if tick.0 < ground_item.expires_tick {
+            continue;
+        }
+        commands.entity(entity).despawn();
+        send_item_end(&mut network_updates, network_id.0, view_tracker, vec![
+            (REASON_KEY, serde_json::Value::String(REASON_EXPIRED.to_string())),
+        ]);
+    }
+}
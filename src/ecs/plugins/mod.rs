@@ -1,5 +1,43 @@
 pub mod websocket;
 pub mod network;
+pub mod simulation;
+pub mod replay;
+pub mod snapshot;
+pub mod persistence;
+pub mod collision;
+pub mod world_map;
+pub mod pathfinding;
+pub mod ai;
+pub mod health;
+pub mod ability;
+pub mod projectile;
+pub mod lag_compensation;
+pub mod anti_cheat;
+pub mod chat;
+pub mod party;
+pub mod inventory;
+pub mod loot;
+pub mod zone;
+pub mod shard;
 
 pub use websocket::WebSocketPlugin;
-pub use network::NetworkPlugin;
\ No newline at end of file
+pub use network::NetworkPlugin;
+pub use simulation::SimulationPlugin;
+pub use replay::ReplayPlugin;
+pub use snapshot::SnapshotPlugin;
+pub use persistence::PersistencePlugin;
+pub use collision::CollisionPlugin;
+pub use world_map::WorldMapPlugin;
+pub use pathfinding::PathfindingPlugin;
+pub use ai::AiPlugin;
+pub use health::HealthPlugin;
+pub use ability::AbilityPlugin;
+pub use projectile::ProjectilePlugin;
+pub use lag_compensation::LagCompensationPlugin;
+pub use anti_cheat::AntiCheatPlugin;
+pub use chat::ChatPlugin;
+pub use party::PartyPlugin;
+pub use inventory::InventoryPlugin;
+pub use loot::LootPlugin;
+pub use zone::ZonePlugin;
+pub use shard::ShardPlugin;
\ No newline at end of file
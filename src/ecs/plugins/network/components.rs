@@ -9,11 +9,50 @@ use std::collections::HashMap;
 #[derive(Component)]
 pub struct NetworkId(pub u32);
 
+// What kind of thing a networked entity is, sent in full syncs so clients can
+// tell players, NPCs, projectiles, ground items and portals apart
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NetworkEntityKind {
+    Player,
+    Npc,
+    Projectile,
+    Item,
+    Portal,
+}
+
+impl NetworkEntityKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NetworkEntityKind::Player => "player",
+            NetworkEntityKind::Npc => "npc",
+            NetworkEntityKind::Projectile => "projectile",
+            NetworkEntityKind::Item => "item",
+            NetworkEntityKind::Portal => "portal",
+        }
+    }
+
+    // How much a client cares about this kind's deltas, relative to the others
+    pub fn priority_weight(&self) -> f32 {
+        match self {
+            NetworkEntityKind::Player => 3.0,
+            NetworkEntityKind::Npc => 2.0,
+            NetworkEntityKind::Projectile => 2.0,
+            NetworkEntityKind::Item => 1.0,
+            NetworkEntityKind::Portal => 0.5,
+        }
+    }
+}
+
 #[derive(Component, Default)]
 pub struct NetworkSnapshot {
     pub components: HashMap<String, serde_json::Value>,
 }
 
+// Entities moving in a straight line: clients extrapolate them from their full sync,
+// so position and velocity are kept in the snapshot but never sent as deltas
+#[derive(Component, Debug, Clone, Copy)]
+pub struct NetworkExtrapolated;
+
 #[derive(Component, Default)]
 pub struct NetworkDirty {
     pub changed_components: Vec<String>,
@@ -22,23 +61,61 @@ pub struct NetworkDirty {
 #[derive(Component, Default)]
 pub struct ViewRangeTracker {
     pub players_in_view: std::collections::HashSet<u32>,
+    // Tick each player in view last came into view
+    pub entered_tick: HashMap<u32, u64>,
+    // Out-of-range players still synced through AlwaysRelevantTo, at a reduced rate
+    pub distant_players: std::collections::HashSet<u32>,
+}
+
+// Changed keys waiting to go to one client, the priority built up while waiting, and
+// how many ticks must pass between sends at the client's current distance
+#[derive(Debug, Default)]
+pub struct PendingDelta {
+    pub keys: Vec<String>,
+    pub priority: f32,
+    pub interval_ticks: u64,
+}
+
+// Per-client delta state for players in view, keyed by player ID. Clients skipped by the
+// bandwidth budget or their rate tier keep their keys here and get the latest values
+// when their turn comes.
+#[derive(Component, Default)]
+pub struct PendingDeltas {
+    pub players: HashMap<u32, PendingDelta>,
+    pub last_sent: HashMap<u32, u64>,
+}
+
+// Players that keep receiving this entity regardless of view distance (e.g. party members)
+#[derive(Component, Default)]
+pub struct AlwaysRelevantTo {
+    pub players: std::collections::HashSet<u32>,
 }
 
 #[derive(Bundle)]
 pub struct NetworkedEntityBundle {
     pub network_id: NetworkId,
+    pub kind: NetworkEntityKind,
     pub snapshot: NetworkSnapshot,
     pub dirty: NetworkDirty,
     pub view_tracker: ViewRangeTracker,
+    pub pending_deltas: PendingDeltas,
+    pub always_relevant_to: AlwaysRelevantTo,
 }
 
 impl NetworkedEntityBundle {
-    pub fn new(network_id: u32) -> Self {
+    pub fn new(network_id: u32, kind: NetworkEntityKind) -> Self {
+        // Kind never changes, so it lives in the snapshot (full syncs) but is never dirty
+        let mut snapshot = NetworkSnapshot::default();
+        snapshot.components.insert(KIND_KEY.to_string(), serde_json::Value::String(kind.as_str().to_string()));
+        
         Self {
             network_id: NetworkId(network_id),
-            snapshot: NetworkSnapshot::default(),
+            kind,
+            snapshot,
             dirty: NetworkDirty::default(),
             view_tracker: ViewRangeTracker::default(),
+            pending_deltas: PendingDeltas::default(),
+            always_relevant_to: AlwaysRelevantTo::default(),
         }
     }
 }
@@ -65,6 +142,41 @@ impl NetworkIdAllocator {
         self.next_id += 1;
         self.next_id
     }
+    
+    pub fn last_allocated(&self) -> u32 {
+        self.next_id
+    }
+    
+    // Skip past IDs issued by a previous run so they are never handed out again
+    pub fn reserve_through(&mut self, id: u32) {
+        self.next_id = self.next_id.max(id);
+    }
+}
+
+// Entities up to this fraction of a client's view range get deltas every interval_ticks
+#[derive(Debug, Clone, Copy)]
+pub struct UpdateRateTier {
+    pub max_distance_fraction: f32,
+    pub interval_ticks: u64,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct NetworkConfig {
+    // Delta bytes each client may be sent per tick; 0 sends everything
+    pub bandwidth_budget_per_tick: usize,
+    // Ordered nearest first
+    pub update_rate_tiers: Vec<UpdateRateTier>,
+    // Entities stay in a client's view at least this long once they enter it
+    pub min_ticks_in_view: u64,
+}
+
+impl NetworkConfig {
+    pub fn update_interval(&self, distance_fraction: f32) -> u64 {
+        self.update_rate_tiers.iter()
+            .find(|tier| distance_fraction <= tier.max_distance_fraction)
+            .or(self.update_rate_tiers.last())
+            .map_or(1, |tier| tier.interval_ticks)
+    }
 }
 
 #[derive(Resource, Default)]
@@ -83,6 +195,9 @@ pub struct NetworkMessage {
     pub message_type: String,
     #[serde(rename = "u")]
     pub entity_updates: Vec<EntityUpdate>,
+    // Simulation tick of the state, on snapshot and delta messages; clients ack it back
+    #[serde(rename = "tk", default, skip_serializing_if = "Option::is_none")]
+    pub tick: Option<u64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -93,11 +208,98 @@ pub struct EntityUpdate {
     pub components: HashMap<String, serde_json::Value>,
 }
 
+// Distant players get a full snapshot of a relevant entity every this many ticks
+pub const DISTANT_SYNC_INTERVAL_TICKS: u64 = 5;
+
+// Speed at which an entity's delta priority doubles
+pub const SPEED_PRIORITY_SCALE: f32 = 100.0;
+
 // Component name mappings for shorter keys
 pub const POSITION_KEY: &str = "p";
 pub const VELOCITY_KEY: &str = "v";
+pub const KIND_KEY: &str = "k";
+pub const HEALTH_KEY: &str = "hp";
+pub const REDIRECT_URL_KEY: &str = "url";
+pub const REDIRECT_TOKEN_KEY: &str = "tok";
 
 // Message type constants
 pub const DELTA_UPDATE_TYPE: &str = "d";
 pub const FULL_SYNC_TYPE: &str = "f";
-pub const WELCOME_TYPE: &str = "w";
\ No newline at end of file
+pub const WELCOME_TYPE: &str = "w";
+pub const ABILITY_EVENT_TYPE: &str = "a";
+pub const PROJECTILE_END_TYPE: &str = "pe";
+pub const CHAT_TYPE: &str = "chat";
+pub const PARTY_TYPE: &str = "party";
+pub const INVENTORY_TYPE: &str = "inv";
+pub const ITEM_END_TYPE: &str = "ie";
+pub const ZONE_CHANGE_TYPE: &str = "z";
+pub const REDIRECT_TYPE: &str = "redirect";
+// ============================================================================
+// MESSAGE AUTHENTICATION
+// ============================================================================
+
+type HmacSha256 = hmac::Hmac<sha2::Sha256>;
+
+// Hex-encoded HMAC-SHA256 of a message under a shared secret
+pub fn sign_message(secret: &str, message: &[u8]) -> String {
+    use hmac::Mac;
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// Checks a hex-encoded HMAC-SHA256 of a message under a shared secret, in constant time
+pub fn verify_message(secret: &str, message: &[u8], signature: &str) -> bool {
+    use hmac::Mac;
+    if !signature.len().is_multiple_of(2) || !signature.is_ascii() {
+        return false;
+    }
+    let Ok(bytes) = (0..signature.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&signature[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+    else {
+        return false;
+    };
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.verify_slice(&bytes).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(tiers: &[(f32, u64)]) -> NetworkConfig {
+        NetworkConfig {
+            bandwidth_budget_per_tick: 0,
+            update_rate_tiers: tiers.iter()
+                .map(|&(max_distance_fraction, interval_ticks)| UpdateRateTier { max_distance_fraction, interval_ticks })
+                .collect(),
+            min_ticks_in_view: 0,
+        }
+    }
+
+    #[test]
+    fn each_tier_covers_distances_up_to_and_including_its_edge() {
+        let config = config(&[(0.5, 1), (0.8, 2), (1.0, 4)]);
+        assert_eq!(config.update_interval(0.0), 1);
+        assert_eq!(config.update_interval(0.5), 1);
+        assert_eq!(config.update_interval(0.6), 2);
+        assert_eq!(config.update_interval(0.8), 2);
+        assert_eq!(config.update_interval(0.9), 4);
+        assert_eq!(config.update_interval(1.0), 4);
+    }
+
+    #[test]
+    fn distances_past_the_last_tier_use_its_rate() {
+        let config = config(&[(0.5, 1), (0.8, 2)]);
+        assert_eq!(config.update_interval(1.0), 2);
+        assert_eq!(config.update_interval(1.4), 2);
+    }
+
+    #[test]
+    fn no_tiers_sends_every_tick() {
+        assert_eq!(config(&[]).update_interval(0.7), 1);
+    }
+}
@@ -2,20 +2,71 @@ pub mod components;
 pub mod systems;
 
 use bevy::prelude::*;
-use components::{NetworkIdAllocator, NetworkUpdates};
-use systems::{detect_velocity_changes_system, detect_position_changes_system, proximity_detection_system, build_delta_updates_system, build_full_sync_system};
+use components::{NetworkConfig, NetworkIdAllocator, NetworkUpdates, UpdateRateTier};
+use systems::{detect_velocity_changes_system, detect_position_changes_system, detect_health_changes_system, proximity_detection_system, build_delta_updates_system, build_full_sync_system};
+
+use crate::ecs::plugins::simulation::components::TICK_RATE_HZ;
+
+const DEFAULT_CLIENT_BANDWIDTH_BYTES_PER_SEC: f64 = 16000.0;
+const DEFAULT_VIEW_MIN_DWELL_SECS: f64 = 1.0;
+// Full rate up close, half rate mid range, quarter rate at the edge of view
+const DEFAULT_UPDATE_RATE_TIERS: [(f32, u64); 3] = [(0.5, 1), (0.8, 2), (1.0, 4)];
 
 // Network plugin for entity synchronization
-pub struct NetworkPlugin;
+pub struct NetworkPlugin {
+    pub client_bandwidth_bytes_per_sec: f64,
+    pub update_rate_tiers: Vec<UpdateRateTier>,
+    pub view_min_dwell_secs: f64,
+}
+
+impl NetworkPlugin {
+    // CLIENT_BANDWIDTH_BYTES_PER_SEC caps the delta traffic sent to each client (0 disables the cap);
+    // DELTA_RATE_TIERS is "fraction:ticks,..." where fraction is of the view range;
+    // VIEW_MIN_DWELL_SECS is how long an entity stays in view before it can leave
+    pub fn from_env() -> Self {
+        let client_bandwidth_bytes_per_sec = std::env::var("CLIENT_BANDWIDTH_BYTES_PER_SEC")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_CLIENT_BANDWIDTH_BYTES_PER_SEC);
+        let mut update_rate_tiers: Vec<UpdateRateTier> = std::env::var("DELTA_RATE_TIERS")
+            .ok()
+            .and_then(|value| value.split(',').map(|tier| {
+                let (fraction, ticks) = tier.split_once(':')?;
+                Some(UpdateRateTier {
+                    max_distance_fraction: fraction.trim().parse().ok()?,
+                    interval_ticks: ticks.trim().parse::<u64>().ok()?.max(1),
+                })
+            }).collect())
+            .unwrap_or_else(|| DEFAULT_UPDATE_RATE_TIERS.iter()
+                .map(|&(max_distance_fraction, interval_ticks)| UpdateRateTier { max_distance_fraction, interval_ticks })
+                .collect());
+        update_rate_tiers.sort_by(|a, b| a.max_distance_fraction.total_cmp(&b.max_distance_fraction));
+        let view_min_dwell_secs = std::env::var("VIEW_MIN_DWELL_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_VIEW_MIN_DWELL_SECS);
+
+        Self { client_bandwidth_bytes_per_sec, update_rate_tiers, view_min_dwell_secs }
+    }
+}
 
 impl Plugin for NetworkPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(NetworkIdAllocator::default())
+            .insert_resource(NetworkConfig {
+                bandwidth_budget_per_tick: (self.client_bandwidth_bytes_per_sec / TICK_RATE_HZ).round() as usize,
+                update_rate_tiers: self.update_rate_tiers.clone(),
+                min_ticks_in_view: (self.view_min_dwell_secs * TICK_RATE_HZ).round() as u64,
+            })
             .insert_resource(NetworkUpdates::default())
             .add_systems(FixedUpdate, (
                 detect_velocity_changes_system.after(crate::ecs::systems::acceleration_friction_system),
                 detect_position_changes_system.after(crate::ecs::systems::movement_system),
-                proximity_detection_system.after(detect_velocity_changes_system).after(detect_position_changes_system),
+                detect_health_changes_system.after(crate::ecs::plugins::health::systems::player_respawn_system),
+                proximity_detection_system
+                    .after(detect_velocity_changes_system)
+                    .after(detect_position_changes_system)
+                    .after(detect_health_changes_system),
                 build_delta_updates_system.after(proximity_detection_system),
                 build_full_sync_system.after(crate::ecs::systems::player_spawn_system).after(proximity_detection_system),
             ));
@@ -1,8 +1,31 @@
 use bevy::prelude::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use crate::ecs::components::{Position, Velocity, Player, ViewDistance};
+use crate::ecs::plugins::health::components::Health;
+use crate::ecs::plugins::simulation::components::SimulationTick;
+use crate::ecs::plugins::zone::components::ZoneId;
 use super::components::*;
 
+// Networked entities whose component T changed this tick
+type ChangedQuery<'w, 's, T> = Query<'w, 's, (
+    &'static mut NetworkDirty,
+    &'static mut NetworkSnapshot,
+    &'static T,
+    Has<NetworkExtrapolated>,
+), (With<NetworkId>, Changed<T>)>;
+
+type DeltaSourceQuery<'w, 's> = Query<'w, 's, (
+    Entity,
+    &'static NetworkId,
+    &'static NetworkEntityKind,
+    &'static mut NetworkDirty,
+    &'static mut PendingDeltas,
+    &'static NetworkSnapshot,
+    &'static Position,
+    Option<&'static Velocity>,
+    &'static ViewRangeTracker,
+)>;
+
 // ============================================================================
 // PRECISION UTILITIES
 // ============================================================================
@@ -17,67 +40,95 @@ fn round_to_2dp(value: f32) -> f32 {
 // ============================================================================
 
 pub fn detect_velocity_changes_system(
-    mut query: Query<(&mut NetworkDirty, &mut NetworkSnapshot, &Velocity), 
-                    (With<NetworkId>, Changed<Velocity>)>,
+    mut query: ChangedQuery<Velocity>,
 ) {
-    for (mut dirty, mut snapshot, velocity) in query.iter_mut() {
+    for (mut dirty, mut snapshot, velocity, extrapolated) in query.iter_mut() {
         // Use compact format: [x, y] instead of {"x": x, "y": y} with 2 decimal precision
         let compact_velocity = vec![round_to_2dp(velocity.x), round_to_2dp(velocity.y)];
         let current_value = serde_json::to_value(compact_velocity).unwrap();
         snapshot.components.insert(super::components::VELOCITY_KEY.to_string(), current_value);
         
-        if !dirty.changed_components.contains(&super::components::VELOCITY_KEY.to_string()) {
+        if !extrapolated && !dirty.changed_components.contains(&super::components::VELOCITY_KEY.to_string()) {
             dirty.changed_components.push(super::components::VELOCITY_KEY.to_string());
         }
     }
 }
 
 pub fn detect_position_changes_system(
-    mut query: Query<(&mut NetworkDirty, &mut NetworkSnapshot, &Position), 
-                    (With<NetworkId>, Changed<Position>)>,
+    mut query: ChangedQuery<Position>,
 ) {
-    for (mut dirty, mut snapshot, position) in query.iter_mut() {
+    for (mut dirty, mut snapshot, position, extrapolated) in query.iter_mut() {
         // Use compact format: [x, y] instead of {"x": x, "y": y} with 2 decimal precision
         let compact_position = vec![round_to_2dp(position.x), round_to_2dp(position.y)];
         let current_value = serde_json::to_value(compact_position).unwrap();
         snapshot.components.insert(super::components::POSITION_KEY.to_string(), current_value);
         
-        if !dirty.changed_components.contains(&super::components::POSITION_KEY.to_string()) {
+        if !extrapolated && !dirty.changed_components.contains(&super::components::POSITION_KEY.to_string()) {
             dirty.changed_components.push(super::components::POSITION_KEY.to_string());
         }
     }
 }
 
+pub fn detect_health_changes_system(
+    mut query: ChangedQuery<Health>,
+) {
+    for (mut dirty, mut snapshot, health, _) in query.iter_mut() {
+        // Use compact format: [current, max]
+        let compact_health = vec![round_to_2dp(health.current), round_to_2dp(health.max)];
+        let current_value = serde_json::to_value(compact_health).unwrap();
+        snapshot.components.insert(super::components::HEALTH_KEY.to_string(), current_value);
+        
+        if !dirty.changed_components.contains(&super::components::HEALTH_KEY.to_string()) {
+            dirty.changed_components.push(super::components::HEALTH_KEY.to_string());
+        }
+    }
+}
+
+// Entities enter view inside the enter radius and leave outside the exit radius, and
+// never before they have been in view for min_ticks_in_view, so one at the edge
+// doesn't trigger a full sync each time it steps back in
 pub fn proximity_detection_system(
+    tick: Res<SimulationTick>,
+    config: Res<NetworkConfig>,
     mut network_updates: ResMut<NetworkUpdates>,
-    mut networked_query: Query<(&NetworkId, &NetworkSnapshot, &Position, &mut ViewRangeTracker)>,
-    player_query: Query<(&Player, &Position, &ViewDistance)>,
+    mut networked_query: Query<(&NetworkId, &NetworkSnapshot, &Position, &ZoneId, &mut ViewRangeTracker, &AlwaysRelevantTo)>,
+    player_query: Query<(&Player, &Position, &ZoneId, &ViewDistance)>,
 ) {
     // For each player, check what entities are in their view range
-    for (player, player_pos, view_distance) in player_query.iter() {
-        let mut entities_in_view = HashSet::new();
+    for (player, player_pos, player_zone, view_distance) in player_query.iter() {
+        let mut entity_distances = HashMap::new();
+        let enter_radius_squared = view_distance.enter_range() * view_distance.enter_range();
+        let exit_radius_squared = view_distance.exit_range() * view_distance.exit_range();
         
-        // Check all networked entities
-        for (network_id, _snapshot, entity_pos, _) in networked_query.iter() {
+        // Check all networked entities; other zones are never visible
+        for (network_id, _snapshot, entity_pos, entity_zone, _, _) in networked_query.iter() {
+            if entity_zone != player_zone {
+                continue;
+            }
+            
             // Calculate distance between player and entity (optimized for ARM)
             let dx = player_pos.x - entity_pos.x;
             let dy = player_pos.y - entity_pos.y;
             let distance_squared = dx * dx + dy * dy; // Faster on ARM than abs()
-            let view_radius_squared = view_distance.radius * view_distance.radius * 1.96; // 1.4^2
-            
-            // Check if entity is within view radius using squared distance
-            if distance_squared <= view_radius_squared {
-                entities_in_view.insert(network_id.0);
-            }
+            entity_distances.insert(network_id.0, distance_squared);
         }
         
         // For each networked entity, check if this player just entered their view
-        for (network_id, snapshot, _entity_pos, mut view_tracker) in networked_query.iter_mut() {
+        for (network_id, snapshot, _entity_pos, entity_zone, mut view_tracker, always_relevant) in networked_query.iter_mut() {
             let was_in_view = view_tracker.players_in_view.contains(&player.id);
-            let is_in_view = entities_in_view.contains(&network_id.0);
+            let is_in_view = match entity_distances.get(&network_id.0) {
+                Some(&distance_squared) if was_in_view => {
+                    let entered_tick = view_tracker.entered_tick.get(&player.id).copied().unwrap_or(0);
+                    distance_squared <= exit_radius_squared || tick.0 - entered_tick < config.min_ticks_in_view
+                }
+                Some(&distance_squared) => distance_squared <= enter_radius_squared,
+                None => false,
+            };
+            let was_distant = view_tracker.distant_players.contains(&player.id);
+            let is_distant = !is_in_view && entity_zone == player_zone && always_relevant.players.contains(&player.id);
             
-            if is_in_view && !was_in_view {
-                // Player just entered view range - send full sync
+            if (is_in_view && !was_in_view) || (is_distant && !was_distant) {
+                // Player just started receiving this entity - send full sync
                 if !snapshot.components.is_empty() {
                     let message = NetworkMessage {
                         message_type: super::components::FULL_SYNC_TYPE.to_string(),
@@ -85,48 +136,137 @@ pub fn proximity_detection_system(
                             network_id: network_id.0,
                             components: snapshot.components.clone(),
                         }],
+                        tick: Some(tick.0),
                     };
-                    network_updates.player_messages.entry(player.id).or_insert_with(Vec::new).push(message);
+                    network_updates.player_messages.entry(player.id).or_default().push(message);
+                }
+            }
+            
+            if is_in_view {
+                if !was_in_view {
+                    view_tracker.entered_tick.insert(player.id, tick.0);
                 }
                 view_tracker.players_in_view.insert(player.id);
-            } else if !is_in_view && was_in_view {
+            } else {
                 // Player left view range
                 view_tracker.players_in_view.remove(&player.id);
+                view_tracker.entered_tick.remove(&player.id);
+            }
+            if is_distant {
+                view_tracker.distant_players.insert(player.id);
+            } else {
+                view_tracker.distant_players.remove(&player.id);
             }
         }
     }
 }
 
+// Priority an entity's pending delta gains each tick it waits for a client: kind
+// weight, scaled up for entities that are close and fast
+fn delta_priority(kind: NetworkEntityKind, distance: f32, view_range: f32, speed: f32) -> f32 {
+    let closeness = (1.0 - distance / view_range).clamp(0.1, 1.0);
+    kind.priority_weight() * closeness * (1.0 + speed / SPEED_PRIORITY_SCALE)
+}
+
+fn encoded_size(update: &EntityUpdate) -> usize {
+    serde_json::to_vec(update).map_or(0, |encoded| encoded.len())
+}
+
+// Changes are queued per client and, once due for the entity's distance tier, sent
+// highest priority first until the client's bandwidth budget for the tick runs out.
+// The rest keep their priority and go later.
 pub fn build_delta_updates_system(
+    tick: Res<SimulationTick>,
+    config: Res<NetworkConfig>,
     mut network_updates: ResMut<NetworkUpdates>,
-    mut dirty_query: Query<(&NetworkId, &mut NetworkDirty, &NetworkSnapshot, &Position, &ViewRangeTracker)>,
+    mut dirty_query: DeltaSourceQuery,
     player_query: Query<(&Player, &Position, &ViewDistance)>,
 ) {
-    // Build updates for each player based on their view radius
+    let distant_sync = tick.0.is_multiple_of(DISTANT_SYNC_INTERVAL_TICKS);
+    let viewers: HashMap<u32, (&Position, &ViewDistance)> = player_query.iter()
+        .map(|(player, position, view_distance)| (player.id, (position, view_distance)))
+        .collect();
+    
+    // Queue this tick's changes for everyone in view and raise the priority of what is waiting
+    for (_, _, kind, mut dirty, mut pending, _, entity_pos, velocity, view_tracker) in dirty_query.iter_mut() {
+        pending.players.retain(|player_id, _| view_tracker.players_in_view.contains(player_id));
+        pending.last_sent.retain(|player_id, _| view_tracker.players_in_view.contains(player_id));
+        let speed = velocity.map_or(0.0, |velocity| Vec2::new(velocity.x, velocity.y).length());
+        
+        for player_id in &view_tracker.players_in_view {
+            if dirty.changed_components.is_empty() && !pending.players.contains_key(player_id) {
+                continue;
+            }
+            let Some((player_pos, view_distance)) = viewers.get(player_id) else {
+                continue;
+            };
+            let pending_delta = pending.players.entry(*player_id).or_default();
+            for component_name in &dirty.changed_components {
+                if !pending_delta.keys.contains(component_name) {
+                    pending_delta.keys.push(component_name.clone());
+                }
+            }
+            let distance = Vec2::new(player_pos.x - entity_pos.x, player_pos.y - entity_pos.y).length();
+            let view_range = view_distance.enter_range();
+            pending_delta.priority += delta_priority(*kind, distance, view_range, speed);
+            pending_delta.interval_ticks = config.update_interval(distance / view_range);
+        }
+        dirty.changed_components.clear();
+    }
+    
+    // Build updates for each player within their budget
     for (player, _player_pos, _view_distance) in player_query.iter() {
         let mut entity_updates = Vec::new();
+        let mut queued = Vec::new();
         
-        for (network_id, mut dirty, snapshot, _entity_pos, view_tracker) in dirty_query.iter_mut() {
-            if dirty.changed_components.is_empty() {
+        for (entity, network_id, _, _, pending, snapshot, _, _, view_tracker) in dirty_query.iter() {
+            // Distant players skip deltas and get the whole snapshot at a reduced rate instead
+            if distant_sync && view_tracker.distant_players.contains(&player.id) {
+                entity_updates.push(EntityUpdate {
+                    network_id: network_id.0,
+                    components: snapshot.components.clone(),
+                });
                 continue;
             }
             
-            // Only send updates if player is in view range
-            if view_tracker.players_in_view.contains(&player.id) {
-                let mut components = HashMap::new();
-                
-                for component_name in &dirty.changed_components {
-                    if let Some(value) = snapshot.components.get(component_name) {
-                        components.insert(component_name.clone(), value.clone());
-                    }
-                }
-                
-                if !components.is_empty() {
-                    entity_updates.push(EntityUpdate {
-                        network_id: network_id.0,
-                        components,
-                    });
-                }
+            // Farther rate tiers wait a few ticks; their changes keep piling into the same keys
+            let Some(pending_delta) = pending.players.get(&player.id) else {
+                continue;
+            };
+            let due = pending.last_sent.get(&player.id)
+                .is_none_or(|last_sent| tick.0 - last_sent >= pending_delta.interval_ticks);
+            if due {
+                queued.push((pending_delta.priority, network_id.0, entity));
+            }
+        }
+        
+        // Ties go to the lower network ID so the order never depends on query order
+        queued.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+        let mut used: usize = entity_updates.iter().map(encoded_size).sum();
+        
+        for (_, network_id, entity) in queued {
+            let Ok((_, _, _, _, mut pending, snapshot, _, _, _)) = dirty_query.get_mut(entity) else {
+                continue;
+            };
+            let Some(pending_delta) = pending.players.get(&player.id) else {
+                continue;
+            };
+            
+            let components: HashMap<String, serde_json::Value> = pending_delta.keys.iter()
+                .filter_map(|key| snapshot.components.get(key).map(|value| (key.clone(), value.clone())))
+                .collect();
+            let update = EntityUpdate { network_id, components };
+            let size = encoded_size(&update);
+            
+            // The top entity always goes out, so one oversized update can't stall the client
+            if config.bandwidth_budget_per_tick > 0 && used > 0 && used + size > config.bandwidth_budget_per_tick {
+                continue;
+            }
+            used += size;
+            pending.players.remove(&player.id);
+            pending.last_sent.insert(player.id, tick.0);
+            if !update.components.is_empty() {
+                entity_updates.push(update);
             }
         }
         
@@ -134,22 +274,19 @@ pub fn build_delta_updates_system(
             let message = NetworkMessage {
                 message_type: super::components::DELTA_UPDATE_TYPE.to_string(),
                 entity_updates,
+                tick: Some(tick.0),
             };
-            network_updates.player_messages.entry(player.id).or_insert_with(Vec::new).push(message);
+            network_updates.player_messages.entry(player.id).or_default().push(message);
         }
     }
-    
-    // Clear dirty components after processing
-    for (_, mut dirty, _, _, _) in dirty_query.iter_mut() {
-        dirty.changed_components.clear();
-    }
 }
 
 pub fn build_full_sync_system(
+    tick: Res<SimulationTick>,
     mut network_updates: ResMut<NetworkUpdates>,
-    networked_query: Query<(&NetworkId, &NetworkSnapshot, &Position)>,
+    networked_query: Query<(&NetworkId, &NetworkSnapshot, &Position, &ZoneId)>,
     mut player_spawn_events: EventReader<crate::ecs::components::PlayerSpawnEvent>,
-    player_query: Query<(&Player, &Position, &ViewDistance)>,
+    player_query: Query<(&Player, &Position, &ZoneId, &ViewDistance)>,
 ) {
     // Get joining players
     let joining_players: Vec<u32> = player_spawn_events.read().map(|event| event.player_id).collect();
@@ -161,19 +298,19 @@ pub fn build_full_sync_system(
     // Send full sync to each joining player based on their view radius
     for joining_player_id in joining_players {
         // Find the joining player's position and view distance
-        if let Some((_, player_pos, view_distance)) = player_query.iter()
-            .find(|(player, _, _)| player.id == joining_player_id) {
+        if let Some((_, player_pos, player_zone, view_distance)) = player_query.iter()
+            .find(|(player, _, _, _)| player.id == joining_player_id) {
             
             let mut entity_updates = Vec::new();
             
             // Send full state of entities within view radius
-            for (network_id, snapshot, entity_pos) in networked_query.iter() {
-                if !snapshot.components.is_empty() {
+            for (network_id, snapshot, entity_pos, entity_zone) in networked_query.iter() {
+                if !snapshot.components.is_empty() && entity_zone == player_zone {
                     // Calculate distance between joining player and entity (ARM optimized)
                     let dx = player_pos.x - entity_pos.x;
                     let dy = player_pos.y - entity_pos.y;
                     let distance_squared = dx * dx + dy * dy; // Faster than abs() on ARM
-                    let view_radius_squared = view_distance.radius * view_distance.radius * 1.96; // 1.4^2
+                    let view_radius_squared = view_distance.enter_range() * view_distance.enter_range();
                     
                     // Only include entities within view radius using squared distance
                     if distance_squared <= view_radius_squared {
@@ -190,9 +327,103 @@ pub fn build_full_sync_system(
                 let message = NetworkMessage {
                     message_type: super::components::FULL_SYNC_TYPE.to_string(),
                     entity_updates,
+                    tick: Some(tick.0),
                 };
-                network_updates.player_messages.entry(joining_player_id).or_insert_with(Vec::new).push(message);
+                network_updates.player_messages.entry(joining_player_id).or_default().push(message);
             }
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    const PLAYER_ID: u32 = 1;
+
+    // A world with one player at the origin and the default rate tiers
+    fn world(bandwidth_budget_per_tick: usize) -> World {
+        let mut world = World::new();
+        world.insert_resource(SimulationTick(0));
+        world.insert_resource(NetworkUpdates::default());
+        world.insert_resource(NetworkConfig {
+            bandwidth_budget_per_tick,
+            update_rate_tiers: vec![
+                UpdateRateTier { max_distance_fraction: 0.5, interval_ticks: 1 },
+                UpdateRateTier { max_distance_fraction: 0.8, interval_ticks: 2 },
+                UpdateRateTier { max_distance_fraction: 1.0, interval_ticks: 4 },
+            ],
+            min_ticks_in_view: 0,
+        });
+        world.spawn((Player { id: PLAYER_ID }, Position { x: 0.0, y: 0.0 }, ViewDistance::default()));
+        world
+    }
+
+    // A networked entity in the player's view at (x, 0)
+    fn spawn(world: &mut World, network_id: u32, kind: NetworkEntityKind, x: f32) -> Entity {
+        let mut bundle = NetworkedEntityBundle::new(network_id, kind);
+        bundle.view_tracker.players_in_view.insert(PLAYER_ID);
+        world.spawn((bundle, Position { x, y: 0.0 })).id()
+    }
+
+    fn change(world: &mut World, entity: Entity, key: &str, value: serde_json::Value) {
+        let mut entity = world.entity_mut(entity);
+        entity.get_mut::<NetworkSnapshot>().unwrap().components.insert(key.to_string(), value);
+        entity.get_mut::<NetworkDirty>().unwrap().changed_components.push(key.to_string());
+    }
+
+    // Runs the delta pass for a tick and returns the updates the player was sent, in order
+    fn deltas(world: &mut World, tick: u64) -> Vec<EntityUpdate> {
+        world.resource_mut::<SimulationTick>().0 = tick;
+        world.resource_mut::<NetworkUpdates>().player_messages.clear();
+        world.run_system_once(build_delta_updates_system).unwrap();
+        world.resource::<NetworkUpdates>().player_messages.get(&PLAYER_ID)
+            .map(|messages| messages.iter().flat_map(|message| message.entity_updates.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    fn ids(updates: &[EntityUpdate]) -> Vec<u32> {
+        updates.iter().map(|update| update.network_id).collect()
+    }
+
+    #[test]
+    fn higher_priority_kinds_and_closer_entities_go_first() {
+        let mut world = world(0);
+        let item = spawn(&mut world, 10001, NetworkEntityKind::Item, 10.0);
+        let npc = spawn(&mut world, 10002, NetworkEntityKind::Npc, 10.0);
+        let far_player = spawn(&mut world, 10003, NetworkEntityKind::Player, 100.0);
+        for entity in [item, npc, far_player] {
+            change(&mut world, entity, POSITION_KEY, serde_json::json!([1.0, 0.0]));
+        }
+        assert_eq!(ids(&deltas(&mut world, 1)), vec![10003, 10002, 10001]);
+    }
+
+    #[test]
+    fn entities_over_budget_are_sent_later_with_their_latest_values() {
+        let mut world = world(40);
+        let npc = spawn(&mut world, 10001, NetworkEntityKind::Npc, 10.0);
+        let item = spawn(&mut world, 10002, NetworkEntityKind::Item, 10.0);
+        change(&mut world, npc, POSITION_KEY, serde_json::json!([10.0, 0.0]));
+        change(&mut world, item, POSITION_KEY, serde_json::json!([10.0, 0.0]));
+        assert_eq!(ids(&deltas(&mut world, 1)), vec![10001]);
+
+        change(&mut world, item, POSITION_KEY, serde_json::json!([12.0, 0.0]));
+        change(&mut world, item, HEALTH_KEY, serde_json::json!([50.0, 100.0]));
+        let updates = deltas(&mut world, 2);
+        assert_eq!(ids(&updates), vec![10002]);
+        assert_eq!(updates[0].components[POSITION_KEY], serde_json::json!([12.0, 0.0]));
+        assert_eq!(updates[0].components[HEALTH_KEY], serde_json::json!([50.0, 100.0]));
+        assert!(deltas(&mut world, 3).is_empty());
+    }
+
+    #[test]
+    fn equal_priorities_go_to_the_lowest_network_id() {
+        let mut world = world(40);
+        let second = spawn(&mut world, 10002, NetworkEntityKind::Item, 10.0);
+        let first = spawn(&mut world, 10001, NetworkEntityKind::Item, 10.0);
+        change(&mut world, second, POSITION_KEY, serde_json::json!([10.0, 0.0]));
+        change(&mut world, first, POSITION_KEY, serde_json::json!([10.0, 0.0]));
+        assert_eq!(ids(&deltas(&mut world, 1)), vec![10001]);
+        assert_eq!(ids(&deltas(&mut world, 2)), vec![10002]);
+    }
+}
@@ -0,0 +1,262 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// ============================================================================
+// PARTY COMMANDS
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PartyCommand {
+    // Invite a player ID into the sender's party (one is formed on acceptance if needed)
+    Invite { player: u32 },
+    // Accept the most recent pending invite
+    Accept,
+    Leave,
+    // Leader only
+    Kick { player: u32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartyFailure {
+    UnknownPlayer,
+    AlreadyInParty,
+    NotLeader,
+    PartyFull,
+    NoInvite,
+    // The inviter left, lost leadership of, or changed party since inviting
+    StaleInvite,
+    NotInParty,
+}
+
+impl PartyFailure {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PartyFailure::UnknownPlayer => "unknown_player",
+            PartyFailure::AlreadyInParty => "already_in_party",
+            PartyFailure::NotLeader => "not_leader",
+            PartyFailure::PartyFull => "party_full",
+            PartyFailure::NoInvite => "no_invite",
+            PartyFailure::StaleInvite => "stale_invite",
+            PartyFailure::NotInParty => "not_in_party",
+        }
+    }
+}
+
+// ============================================================================
+// PARTY RESOURCES
+// ============================================================================
+
+#[derive(Debug, Clone)]
+pub struct Party {
+    pub leader: u32,
+    // Player IDs in join order; the longest-standing member inherits leadership
+    pub members: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PartyInvite {
+    pub inviter: u32,
+    // The inviter's party when the invite was sent (None if they had none)
+    pub party_id: Option<u32>,
+    pub expires_tick: u64,
+}
+
+#[derive(Resource, Default)]
+pub struct Parties {
+    pub parties: HashMap<u32, Party>,
+    // Player ID -> party ID
+    pub membership: HashMap<u32, u32>,
+    // Invitee player ID -> their latest invite
+    pub invites: HashMap<u32, PartyInvite>,
+    next_id: u32,
+}
+
+impl Parties {
+    pub fn party_of(&self, player_id: u32) -> Option<(u32, &Party)> {
+        let party_id = *self.membership.get(&player_id)?;
+        self.parties.get(&party_id).map(|party| (party_id, party))
+    }
+
+    pub fn same_party(&self, a: u32, b: u32) -> bool {
+        self.membership.get(&a).is_some_and(|party| self.membership.get(&b) == Some(party))
+    }
+
+    pub fn create(&mut self, leader: u32) -> u32 {
+        self.next_id += 1;
+        self.parties.insert(self.next_id, Party { leader, members: vec![leader] });
+        self.membership.insert(leader, self.next_id);
+        self.next_id
+    }
+
+    pub fn join(&mut self, party_id: u32, player_id: u32) {
+        if let Some(party) = self.parties.get_mut(&party_id) {
+            party.members.push(player_id);
+            self.membership.insert(player_id, party_id);
+        }
+    }
+
+    // Puts the invitee in the party the invite was sent from, forming it if the inviter
+    // had none. The invite only stands while the inviter still leads that party, or is
+    // still without one.
+    pub fn accept(&mut self, invite: &PartyInvite, invitee: u32, max_size: usize) -> Result<u32, PartyFailure> {
+        let party_id = match (invite.party_id, self.party_of(invite.inviter)) {
+            (Some(invited_to), Some((party_id, party))) if invited_to == party_id && party.leader == invite.inviter => {
+                if party.members.len() >= max_size {
+                    return Err(PartyFailure::PartyFull);
+                }
+                party_id
+            }
+            (None, None) => self.create(invite.inviter),
+            _ => return Err(PartyFailure::StaleInvite),
+        };
+        self.join(party_id, invitee);
+        Ok(party_id)
+    }
+
+    // Removes a player from their party, handing leadership on and disbanding
+    // parties left with a single member
+    pub fn remove(&mut self, player_id: u32) -> Option<PartyDeparture> {
+        let party_id = self.membership.remove(&player_id)?;
+        let party = self.parties.get_mut(&party_id)?;
+        party.members.retain(|member| *member != player_id);
+
+        if party.members.len() < 2 {
+            let remaining = self.parties.remove(&party_id).map(|party| party.members).unwrap_or_default();
+            for member in &remaining {
+                self.membership.remove(member);
+            }
+            return Some(PartyDeparture { party_id, remaining, disbanded: true });
+        }
+        if party.leader == player_id {
+            party.leader = party.members[0];
+        }
+        Some(PartyDeparture { party_id, remaining: party.members.clone(), disbanded: false })
+    }
+}
+
+pub struct PartyDeparture {
+    pub party_id: u32,
+    pub remaining: Vec<u32>,
+    pub disbanded: bool,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct PartyConfig {
+    pub max_size: usize,
+    pub invite_timeout_ticks: u64,
+}
+
+// ============================================================================
+// PARTY MESSAGE KEYS
+// ============================================================================
+
+pub const STAGE_KEY: &str = "st";
+pub const LEADER_KEY: &str = "ld";
+pub const MEMBERS_KEY: &str = "m";
+pub const INVITER_KEY: &str = "fr";
+pub const REASON_KEY: &str = "r";
+
+pub const STAGE_INVITE: &str = "invite";
+pub const STAGE_ROSTER: &str = "roster";
+pub const STAGE_FAIL: &str = "fail";
+
+pub const REASON_LEFT: &str = "left";
+pub const REASON_KICKED: &str = "kicked";
+pub const REASON_DISBANDED: &str = "disbanded";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invite_from(parties: &Parties, inviter: u32) -> PartyInvite {
+        PartyInvite { inviter, party_id: parties.party_of(inviter).map(|(party_id, _)| party_id), expires_tick: 100 }
+    }
+
+    // Party led by 1 with members 1, 2, 3 in join order
+    fn party_of_three(parties: &mut Parties) -> u32 {
+        let party_id = parties.create(1);
+        parties.join(party_id, 2);
+        parties.join(party_id, 3);
+        party_id
+    }
+
+    #[test]
+    fn accepting_an_invite_from_a_loner_forms_a_party() {
+        let mut parties = Parties::default();
+        let invite = invite_from(&parties, 1);
+        let party_id = parties.accept(&invite, 2, 4).unwrap();
+        let (_, party) = parties.party_of(2).unwrap();
+        assert_eq!(party.leader, 1);
+        assert_eq!(party.members, vec![1, 2]);
+        assert!(parties.same_party(1, 2));
+        assert_eq!(parties.party_of(1).map(|(id, _)| id), Some(party_id));
+    }
+
+    #[test]
+    fn full_parties_turn_invitees_away() {
+        let mut parties = Parties::default();
+        party_of_three(&mut parties);
+        let invite = invite_from(&parties, 1);
+        assert_eq!(parties.accept(&invite, 4, 3).err(), Some(PartyFailure::PartyFull));
+        assert!(parties.party_of(4).is_none());
+    }
+
+    #[test]
+    fn leadership_passes_to_the_longest_standing_member() {
+        let mut parties = Parties::default();
+        let party_id = party_of_three(&mut parties);
+        let departure = parties.remove(1).unwrap();
+        assert!(!departure.disbanded);
+        assert_eq!(departure.remaining, vec![2, 3]);
+        assert_eq!(parties.parties[&party_id].leader, 2);
+        assert!(parties.party_of(1).is_none());
+    }
+
+    #[test]
+    fn a_party_left_with_one_member_disbands() {
+        let mut parties = Parties::default();
+        let party_id = parties.create(1);
+        parties.join(party_id, 2);
+        let departure = parties.remove(2).unwrap();
+        assert!(departure.disbanded);
+        assert_eq!(departure.remaining, vec![1]);
+        assert!(parties.parties.is_empty());
+        assert!(parties.membership.is_empty());
+        assert!(parties.remove(1).is_none());
+    }
+
+    #[test]
+    fn invites_go_stale_when_the_inviter_loses_leadership() {
+        let mut parties = Parties::default();
+        party_of_three(&mut parties);
+        let invite = invite_from(&parties, 1);
+        parties.remove(1);
+        parties.create(1);
+        assert_eq!(parties.accept(&invite, 4, 4).err(), Some(PartyFailure::StaleInvite));
+
+        let mut parties = Parties::default();
+        let party_id = party_of_three(&mut parties);
+        let invite = invite_from(&parties, 1);
+        parties.remove(1);
+        parties.join(party_id, 1);
+        assert_eq!(parties.accept(&invite, 4, 4).err(), Some(PartyFailure::StaleInvite));
+        assert!(parties.party_of(4).is_none());
+    }
+
+    #[test]
+    fn invites_go_stale_when_the_inviter_joins_or_leaves_a_party() {
+        let mut parties = Parties::default();
+        let loner_invite = invite_from(&parties, 1);
+        let other = parties.create(5);
+        parties.join(other, 1);
+        assert_eq!(parties.accept(&loner_invite, 4, 4).err(), Some(PartyFailure::StaleInvite));
+
+        let mut parties = Parties::default();
+        let party_id = parties.create(1);
+        parties.join(party_id, 2);
+        let leader_invite = invite_from(&parties, 1);
+        parties.remove(2);
+        assert_eq!(parties.accept(&leader_invite, 4, 4).err(), Some(PartyFailure::StaleInvite));
+    }
+}
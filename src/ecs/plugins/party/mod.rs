@@ -0,0 +1,49 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::{Parties, PartyConfig};
+use systems::{party_command_system, party_visibility_system};
+use crate::ecs::plugins::simulation::components::TICK_RATE_HZ;
+
+const DEFAULT_MAX_SIZE: usize = 5;
+const DEFAULT_INVITE_TIMEOUT_SECS: f64 = 60.0;
+
+// Party plugin: invite, accept, leave and kick, with a leader and roster pushes.
+// Party members stay networked to each other whatever the distance.
+pub struct PartyPlugin {
+    pub max_size: usize,
+    pub invite_timeout_secs: f64,
+}
+
+impl PartyPlugin {
+    // PARTY_MAX_SIZE caps party size; PARTY_INVITE_TIMEOUT_SECS is how long invites stay valid
+    pub fn from_env() -> Self {
+        let max_size = std::env::var("PARTY_MAX_SIZE")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_MAX_SIZE);
+        let invite_timeout_secs = std::env::var("PARTY_INVITE_TIMEOUT_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_INVITE_TIMEOUT_SECS);
+
+        Self { max_size, invite_timeout_secs }
+    }
+}
+
+impl Plugin for PartyPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(PartyConfig {
+                max_size: self.max_size,
+                invite_timeout_ticks: (self.invite_timeout_secs * TICK_RATE_HZ).round() as u64,
+            })
+            .insert_resource(Parties::default())
+            .add_systems(FixedUpdate, (
+                party_command_system,
+                party_visibility_system,
+            ).chain()
+                .after(crate::ecs::systems::input_processing_system)
+                .before(crate::ecs::plugins::network::systems::proximity_detection_system));
+    }
+}
@@ -0,0 +1,216 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::ecs::components::*;
+use crate::ecs::plugins::network::components::{AlwaysRelevantTo, EntityUpdate, NetworkId, NetworkMessage, NetworkUpdates, PARTY_TYPE};
+use crate::ecs::plugins::simulation::components::SimulationTick;
+use super::components::*;
+
+// ============================================================================
+// PARTY MESSAGES
+// ============================================================================
+
+// Party messages are keyed by party ID (0 when the player has no party)
+fn party_message(party_id: u32, stage: &str, fields: Vec<(&str, serde_json::Value)>) -> NetworkMessage {
+    let mut components = HashMap::new();
+    components.insert(STAGE_KEY.to_string(), serde_json::Value::String(stage.to_string()));
+    for (key, value) in fields {
+        components.insert(key.to_string(), value);
+    }
+    NetworkMessage {
+        message_type: PARTY_TYPE.to_string(),
+        entity_updates: vec![EntityUpdate { network_id: party_id, components }],
+        tick: None,
+    }
+}
+
+fn send_failure(network_updates: &mut NetworkUpdates, player_id: u32, party_id: u32, failure: PartyFailure) {
+    let message = party_message(party_id, STAGE_FAIL, vec![
+        (REASON_KEY, serde_json::Value::String(failure.as_str().to_string())),
+    ]);
+    network_updates.player_messages.entry(player_id).or_default().push(message);
+}
+
+// Tells a player they are no longer in the party
+fn send_removed(network_updates: &mut NetworkUpdates, player_id: u32, party_id: u32, reason: &str) {
+    let message = party_message(party_id, STAGE_ROSTER, vec![
+        (MEMBERS_KEY, serde_json::json!([])),
+        (REASON_KEY, serde_json::Value::String(reason.to_string())),
+    ]);
+    network_updates.player_messages.entry(player_id).or_default().push(message);
+}
+
+// Pushes the current roster, as [player_id, network_id] pairs, to every member
+fn push_roster(
+    network_updates: &mut NetworkUpdates,
+    parties: &Parties,
+    party_id: u32,
+    player_registry: &PlayerRegistry,
+    network_ids: &Query<&NetworkId>,
+) {
+    let Some(party) = parties.parties.get(&party_id) else {
+        return;
+    };
+    let members: Vec<serde_json::Value> = party.members.iter()
+        .map(|member| {
+            let network_id = player_registry.get_player_entity(*member)
+                .and_then(|entity| network_ids.get(entity).ok())
+                .map_or(0, |network_id| network_id.0);
+            serde_json::json!([member, network_id])
+        })
+        .collect();
+
+    let message = party_message(party_id, STAGE_ROSTER, vec![
+        (LEADER_KEY, serde_json::Value::from(party.leader)),
+        (MEMBERS_KEY, serde_json::Value::Array(members)),
+    ]);
+    for member in &party.members {
+        network_updates.player_messages.entry(*member).or_default().push(message.clone());
+    }
+}
+
+fn notify_departure(
+    network_updates: &mut NetworkUpdates,
+    parties: &Parties,
+    departure: &PartyDeparture,
+    player_registry: &PlayerRegistry,
+    network_ids: &Query<&NetworkId>,
+) {
+    if departure.disbanded {
+        for member in &departure.remaining {
+            send_removed(network_updates, *member, departure.party_id, REASON_DISBANDED);
+        }
+    } else {
+        push_roster(network_updates, parties, departure.party_id, player_registry, network_ids);
+    }
+}
+
+// ============================================================================
+// PARTY SYSTEMS
+// ============================================================================
+
+#[allow(clippy::too_many_arguments)]
+pub fn party_command_system(
+    tick: Res<SimulationTick>,
+    config: Res<PartyConfig>,
+    mut parties: ResMut<Parties>,
+    player_registry: Res<PlayerRegistry>,
+    mut network_updates: ResMut<NetworkUpdates>,
+    mut input_events: EventReader<InputCommandEvent>,
+    mut despawn_events: EventReader<PlayerDespawnEvent>,
+    network_ids: Query<&NetworkId>,
+) {
+    for event in input_events.read() {
+        let InputCommand::Party(command) = &event.command else {
+            continue;
+        };
+        let sender = event.player_id;
+        let sender_party = parties.party_of(sender).map(|(party_id, party)| (party_id, party.leader, party.members.len()));
+        let sender_party_id = sender_party.map_or(0, |(party_id, _, _)| party_id);
+
+        match command {
+            PartyCommand::Invite { player } => {
+                if *player == sender || player_registry.get_player_entity(*player).is_none() {
+                    send_failure(&mut network_updates, sender, sender_party_id, PartyFailure::UnknownPlayer);
+                    continue;
+                }
+                if parties.membership.contains_key(player) {
+                    send_failure(&mut network_updates, sender, sender_party_id, PartyFailure::AlreadyInParty);
+                    continue;
+                }
+                if let Some((_, leader, size)) = sender_party {
+                    if leader != sender {
+                        send_failure(&mut network_updates, sender, sender_party_id, PartyFailure::NotLeader);
+                        continue;
+                    }
+                    if size >= config.max_size {
+                        send_failure(&mut network_updates, sender, sender_party_id, PartyFailure::PartyFull);
+                        continue;
+                    }
+                }
+
+                parties.invites.insert(*player, PartyInvite {
+                    inviter: sender,
+                    party_id: sender_party.map(|(party_id, _, _)| party_id),
+                    expires_tick: tick.0 + config.invite_timeout_ticks,
+                });
+                let message = party_message(sender_party_id, STAGE_INVITE, vec![
+                    (INVITER_KEY, serde_json::Value::from(sender)),
+                ]);
+                network_updates.player_messages.entry(*player).or_default().push(message);
+            }
+            PartyCommand::Accept => {
+                if sender_party.is_some() {
+                    send_failure(&mut network_updates, sender, sender_party_id, PartyFailure::AlreadyInParty);
+                    continue;
+                }
+                let invite = parties.invites.remove(&sender)
+                    .filter(|invite| tick.0 < invite.expires_tick)
+                    .filter(|invite| player_registry.get_player_entity(invite.inviter).is_some());
+                let Some(invite) = invite else {
+                    send_failure(&mut network_updates, sender, 0, PartyFailure::NoInvite);
+                    continue;
+                };
+
+                let party_id = match parties.accept(&invite, sender, config.max_size) {
+                    Ok(party_id) => party_id,
+                    Err(failure) => {
+                        send_failure(&mut network_updates, sender, 0, failure);
+                        continue;
+                    }
+                };
+                println!("🤝 Player {} joined party {}", sender, party_id);
+                push_roster(&mut network_updates, &parties, party_id, &player_registry, &network_ids);
+            }
+            PartyCommand::Leave => {
+                let Some(departure) = parties.remove(sender) else {
+                    send_failure(&mut network_updates, sender, 0, PartyFailure::NotInParty);
+                    continue;
+                };
+                send_removed(&mut network_updates, sender, departure.party_id, REASON_LEFT);
+                notify_departure(&mut network_updates, &parties, &departure, &player_registry, &network_ids);
+            }
+            PartyCommand::Kick { player } => {
+                let Some((party_id, leader, _)) = sender_party else {
+                    send_failure(&mut network_updates, sender, 0, PartyFailure::NotInParty);
+                    continue;
+                };
+                if leader != sender {
+                    send_failure(&mut network_updates, sender, party_id, PartyFailure::NotLeader);
+                    continue;
+                }
+                if *player == sender || parties.membership.get(player) != Some(&party_id) {
+                    send_failure(&mut network_updates, sender, party_id, PartyFailure::UnknownPlayer);
+                    continue;
+                }
+                let Some(departure) = parties.remove(*player) else {
+                    continue;
+                };
+                send_removed(&mut network_updates, *player, party_id, REASON_KICKED);
+                notify_departure(&mut network_updates, &parties, &departure, &player_registry, &network_ids);
+            }
+        }
+    }
+
+    // Disconnecting leaves the party
+    for event in despawn_events.read() {
+        parties.invites.remove(&event.player_id);
+        if let Some(departure) = parties.remove(event.player_id) {
+            notify_departure(&mut network_updates, &parties, &departure, &player_registry, &network_ids);
+        }
+    }
+}
+
+// Keeps party members relevant to each other for networking, whatever the distance
+pub fn party_visibility_system(
+    parties: Res<Parties>,
+    mut players: Query<(&Player, &mut AlwaysRelevantTo)>,
+) {
+    if !parties.is_changed() {
+        return;
+    }
+    for (player, mut always_relevant) in players.iter_mut() {
+        always_relevant.players = parties.party_of(player.id)
+            .map(|(_, party)| party.members.iter().copied().filter(|member| *member != player.id).collect())
+            .unwrap_or_default();
+    }
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use std::collections::{HashMap, VecDeque};
+
+// ============================================================================
+// PATHFINDING COMPONENTS
+// ============================================================================
+
+// Steers DesiredVelocity through a list of waypoints at the entity's max speed
+#[derive(Component, Debug, Clone)]
+pub struct PathFollower {
+    pub waypoints: VecDeque<Vec2>,
+    pub arrive_radius: f32,
+}
+
+impl Default for PathFollower {
+    fn default() -> Self {
+        Self {
+            waypoints: VecDeque::new(),
+            arrive_radius: 8.0,
+        }
+    }
+}
+
+// ============================================================================
+// PATHFINDING EVENTS
+// ============================================================================
+
+// Ask the pathfinding service to route an entity to a goal position
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PathRequestEvent {
+    pub entity: Entity,
+    pub goal: Vec2,
+}
+
+// ============================================================================
+// PATHFINDING RESOURCES
+// ============================================================================
+
+#[derive(Resource)]
+pub struct PathfindingConfig {
+    // Requests solved per tick; the rest wait in the queue
+    pub max_paths_per_tick: usize,
+    // A* gives up after expanding this many tiles
+    pub max_expanded_nodes: usize,
+}
+
+impl Default for PathfindingConfig {
+    fn default() -> Self {
+        Self {
+            max_paths_per_tick: 8,
+            max_expanded_nodes: 4000,
+        }
+    }
+}
+
+// Pending requests, at most one per entity (a newer request replaces the old goal
+// but keeps its place in line). The goal map doubles as the set of queued entities.
+#[derive(Resource, Default)]
+pub struct PathfindingQueue {
+    pub pending: VecDeque<Entity>,
+    pub goals: HashMap<Entity, Vec2>,
+}
+
+impl PathfindingQueue {
+    pub fn push(&mut self, entity: Entity, goal: Vec2) {
+        if self.goals.insert(entity, goal).is_none() {
+            self.pending.push_back(entity);
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<(Entity, Vec2)> {
+        let entity = self.pending.pop_front()?;
+        let goal = self.goals.remove(&entity)?;
+        Some((entity, goal))
+    }
+}
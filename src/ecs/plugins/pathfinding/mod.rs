@@ -0,0 +1,22 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::{PathRequestEvent, PathfindingConfig, PathfindingQueue};
+use systems::{path_follow_system, process_path_requests_system, queue_path_requests_system};
+
+// Pathfinding plugin: budgeted A* over the world map tiles and waypoint steering
+pub struct PathfindingPlugin;
+
+impl Plugin for PathfindingPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(PathfindingConfig::default())
+            .insert_resource(PathfindingQueue::default())
+            .add_event::<PathRequestEvent>()
+            .add_systems(FixedUpdate, (
+                queue_path_requests_system,
+                process_path_requests_system,
+                path_follow_system,
+            ).chain().before(crate::ecs::systems::acceleration_friction_system));
+    }
+}
@@ -0,0 +1,275 @@
+use bevy::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use crate::ecs::components::{CharacterProfile, DesiredVelocity, Position};
+use crate::ecs::plugins::collision::components::Collider;
+use crate::ecs::plugins::world_map::components::WorldMap;
+use crate::ecs::plugins::zone::components::{ZoneId, MAIN_ZONE};
+use super::components::*;
+
+// Integer step costs keep A* results identical across platforms
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+const DEFAULT_BODY_RADIUS: f32 = 16.0;
+
+// ============================================================================
+// PATHFINDING HELPERS
+// ============================================================================
+
+fn octile_distance(from: (i32, i32), to: (i32, i32)) -> u32 {
+    let dx = (from.0 - to.0).unsigned_abs();
+    let dy = (from.1 - to.1).unsigned_abs();
+    STRAIGHT_COST * dx.max(dy) + (DIAGONAL_COST - STRAIGHT_COST) * dx.min(dy)
+}
+
+// A* over map tiles with 8-way movement. Diagonal steps are only allowed when both
+// adjacent orthogonal tiles are open, so paths never clip wall corners.
+pub fn find_tile_path(map: &WorldMap, start: (i32, i32), goal: (i32, i32), max_expanded: usize) -> Option<Vec<(i32, i32)>> {
+    if map.is_blocked(goal.0, goal.1) {
+        return None;
+    }
+
+    let width = map.width as i32;
+    let height = map.height as i32;
+    let in_bounds = |(x, y): (i32, i32)| x >= 0 && y >= 0 && x < width && y < height;
+    if !in_bounds(start) || !in_bounds(goal) {
+        return None;
+    }
+
+    let index = |(x, y): (i32, i32)| (y * width + x) as usize;
+    let mut g_score = vec![u32::MAX; (width * height) as usize];
+    let mut came_from = vec![None; (width * height) as usize];
+    let mut open = BinaryHeap::new();
+
+    g_score[index(start)] = 0;
+    open.push(Reverse((octile_distance(start, goal), 0u32, start)));
+    let mut expanded = 0;
+
+    while let Some(Reverse((_, g, tile))) = open.pop() {
+        if tile == goal {
+            let mut path = vec![goal];
+            let mut current = goal;
+            while let Some(previous) = came_from[index(current)] {
+                path.push(previous);
+                current = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        // Skip stale heap entries
+        if g > g_score[index(tile)] {
+            continue;
+        }
+
+        expanded += 1;
+        if expanded > max_expanded {
+            return None;
+        }
+
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                let next = (tile.0 + dx, tile.1 + dy);
+                if !in_bounds(next) || map.is_blocked(next.0, next.1) {
+                    continue;
+                }
+
+                let diagonal = dx != 0 && dy != 0;
+                if diagonal && (map.is_blocked(tile.0 + dx, tile.1) || map.is_blocked(tile.0, tile.1 + dy)) {
+                    continue;
+                }
+
+                let next_g = g + if diagonal { DIAGONAL_COST } else { STRAIGHT_COST };
+                if next_g < g_score[index(next)] {
+                    g_score[index(next)] = next_g;
+                    came_from[index(next)] = Some(tile);
+                    open.push(Reverse((next_g + octile_distance(next, goal), next_g, next)));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// Samples the segment at quarter-tile steps to check a body can travel it
+fn has_clear_line(map: &WorldMap, from: Vec2, to: Vec2, radius: f32) -> bool {
+    let distance = from.distance(to);
+    let steps = (distance / (map.tile_size * 0.25)).ceil().max(1.0) as u32;
+    (0..=steps).all(|step| {
+        let point = from.lerp(to, step as f32 / steps as f32);
+        !map.overlaps_blocked(point, radius)
+    })
+}
+
+// Drops intermediate tile centers that can be skipped in a straight line
+fn smooth_path(map: &WorldMap, start: Vec2, points: Vec<Vec2>, radius: f32) -> VecDeque<Vec2> {
+    let mut smoothed = VecDeque::new();
+    let mut anchor = start;
+    let mut index = 0;
+
+    while index < points.len() {
+        let mut furthest = index;
+        while furthest + 1 < points.len() && has_clear_line(map, anchor, points[furthest + 1], radius) {
+            furthest += 1;
+        }
+        anchor = points[furthest];
+        smoothed.push_back(anchor);
+        index = furthest + 1;
+    }
+
+    smoothed
+}
+
+// ============================================================================
+// PATHFINDING SYSTEMS
+// ============================================================================
+
+pub fn queue_path_requests_system(
+    mut requests: EventReader<PathRequestEvent>,
+    mut queue: ResMut<PathfindingQueue>,
+) {
+    for request in requests.read() {
+        queue.push(request.entity, request.goal);
+    }
+}
+
+// Solves a bounded number of queued requests per tick so large NPC counts can't
+// stall FixedUpdate; leftover requests are picked up on later ticks
+pub fn process_path_requests_system(
+    config: Res<PathfindingConfig>,
+    map: Option<Res<WorldMap>>,
+    mut queue: ResMut<PathfindingQueue>,
+    mut query: Query<(&Position, &ZoneId, &mut PathFollower, &mut DesiredVelocity, Option<&Collider>)>,
+) {
+    for _ in 0..config.max_paths_per_tick {
+        let Some((entity, goal)) = queue.pop() else {
+            break;
+        };
+        let Ok((position, zone, mut follower, mut desired_velocity, collider)) = query.get_mut(entity) else {
+            continue;
+        };
+
+        let start = Vec2::new(position.x, position.y);
+        let Some(map) = map.as_deref().filter(|_| *zone == MAIN_ZONE) else {
+            // Without a map (it only covers the main zone) every straight line is walkable
+            follower.waypoints = VecDeque::from([goal]);
+            continue;
+        };
+
+        let start_tile = (map.tile_of(start.x), map.tile_of(start.y));
+        let goal_tile = (map.tile_of(goal.x), map.tile_of(goal.y));
+        let radius = collider.map(|collider| collider.radius).unwrap_or(DEFAULT_BODY_RADIUS);
+
+        match find_tile_path(map, start_tile, goal_tile, config.max_expanded_nodes) {
+            Some(tiles) => {
+                // Walk through tile centers, but finish exactly at the requested goal
+                let mut points: Vec<Vec2> = tiles.iter().skip(1).map(|(x, y)| map.tile_center(*x, *y)).collect();
+                points.pop();
+                points.push(goal);
+                follower.waypoints = smooth_path(map, start, points, radius);
+            }
+            None => {
+                // Stop rather than keep walking toward the last path's waypoint
+                follower.waypoints.clear();
+                desired_velocity.x = 0.0;
+                desired_velocity.y = 0.0;
+            }
+        }
+    }
+}
+
+pub fn path_follow_system(
+    mut query: Query<(&Position, &CharacterProfile, &mut PathFollower, &mut DesiredVelocity)>,
+) {
+    for (position, profile, mut follower, mut desired_velocity) in query.iter_mut() {
+        let current = Vec2::new(position.x, position.y);
+
+        while follower.waypoints.front().is_some_and(|waypoint| waypoint.distance(current) <= follower.arrive_radius) {
+            follower.waypoints.pop_front();
+            if follower.waypoints.is_empty() {
+                desired_velocity.x = 0.0;
+                desired_velocity.y = 0.0;
+            }
+        }
+
+        if let Some(waypoint) = follower.waypoints.front() {
+            let direction = (*waypoint - current).normalize_or_zero();
+            desired_velocity.x = direction.x * profile.max_speed;
+            desired_velocity.y = direction.y * profile.max_speed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(rows: &[&str]) -> WorldMap {
+        WorldMap {
+            id: "test".to_string(),
+            hash: 0,
+            tile_size: 32.0,
+            width: rows[0].len() as u32,
+            height: rows.len() as u32,
+            blocked: rows.iter().flat_map(|row| row.chars().map(|tile| tile == '#')).collect(),
+        }
+    }
+
+    #[test]
+    fn walks_straight_across_open_ground() {
+        let map = map(&[
+            ".....",
+            ".....",
+        ]);
+        let path = find_tile_path(&map, (0, 0), (4, 0), 100).unwrap();
+        assert_eq!(path, vec![(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)]);
+    }
+
+    #[test]
+    fn routes_around_a_wall_without_cutting_corners() {
+        let map = map(&[
+            "..#..",
+            "..#..",
+            ".....",
+        ]);
+        let path = find_tile_path(&map, (0, 0), (4, 0), 100).unwrap();
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(4, 0)));
+        assert!(path.iter().all(|tile| !map.is_blocked(tile.0, tile.1)));
+        assert!(path.contains(&(2, 2)), "path should pass under the wall: {:?}", path);
+        for step in path.windows(2) {
+            let (dx, dy) = (step[1].0 - step[0].0, step[1].1 - step[0].1);
+            assert!(dx.abs() <= 1 && dy.abs() <= 1);
+            if dx != 0 && dy != 0 {
+                assert!(!map.is_blocked(step[0].0 + dx, step[0].1) && !map.is_blocked(step[0].0, step[0].1 + dy));
+            }
+        }
+    }
+
+    #[test]
+    fn unreachable_or_blocked_goals_have_no_path() {
+        let map = map(&[
+            "..#..",
+            "..#..",
+            "..#.#",
+        ]);
+        assert_eq!(find_tile_path(&map, (0, 0), (4, 0), 1000), None);
+        assert_eq!(find_tile_path(&map, (0, 0), (2, 0), 1000), None);
+        assert_eq!(find_tile_path(&map, (0, 0), (9, 0), 1000), None);
+    }
+
+    #[test]
+    fn gives_up_once_the_expansion_budget_is_spent() {
+        let map = map(&[
+            "....................",
+            "....................",
+            "....................",
+        ]);
+        assert_eq!(find_tile_path(&map, (0, 1), (19, 1), 5), None);
+        assert_eq!(find_tile_path(&map, (0, 1), (19, 1), 100).map(|path| path.len()), Some(20));
+    }
+}
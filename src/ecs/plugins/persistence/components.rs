@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use crate::ecs::components::{CharacterProfile, Position};
+use crate::ecs::plugins::health::components::SavedHealth;
+use crate::ecs::plugins::inventory::components::ItemStack;
+
+// ============================================================================
+// PLAYER PROFILE DATA
+// ============================================================================
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredPlayerProfile {
+    pub position: Position,
+    pub profile: CharacterProfile,
+    // Zone name (the template's, for a player in an instance); missing means the main zone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone: Option<String>,
+    // Missing for profiles saved before inventories were, which start with the starting items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inventory: Option<Vec<Option<ItemStack>>>,
+    // Missing for profiles saved before health was, which start at full health
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<SavedHealth>,
+}
+
+// ============================================================================
+// STORAGE BACKENDS
+// ============================================================================
+
+// Storage backend for player profiles, keyed by account ID
+pub trait PlayerProfileStore: Send + Sync + 'static {
+    fn load(&self, account_id: &str) -> std::io::Result<Option<StoredPlayerProfile>>;
+    fn save(&self, account_id: &str, profile: &StoredPlayerProfile) -> std::io::Result<()>;
+}
+
+// One JSON file per account inside a directory
+pub struct FilePlayerProfileStore {
+    pub dir: PathBuf,
+}
+
+impl FilePlayerProfileStore {
+    pub fn new(dir: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    // Account IDs are validated at connection time, so they are safe file names
+    fn profile_path(&self, account_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", account_id))
+    }
+}
+
+impl PlayerProfileStore for FilePlayerProfileStore {
+    fn load(&self, account_id: &str) -> std::io::Result<Option<StoredPlayerProfile>> {
+        match std::fs::read_to_string(self.profile_path(account_id)) {
+            Ok(data) => Ok(Some(serde_json::from_str(&data)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn save(&self, account_id: &str, profile: &StoredPlayerProfile) -> std::io::Result<()> {
+        let path = self.profile_path(account_id);
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec(profile)?)?;
+        std::fs::rename(tmp_path, path)
+    }
+}
+
+// ============================================================================
+// PERSISTENCE RESOURCES
+// ============================================================================
+
+#[derive(Resource)]
+pub struct PlayerProfileStorage {
+    pub store: Box<dyn PlayerProfileStore>,
+    pub save_interval_ticks: u64,
+}
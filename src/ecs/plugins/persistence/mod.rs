@@ -0,0 +1,60 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::{FilePlayerProfileStore, PlayerProfileStorage};
+use systems::{load_player_profile_system, periodic_profile_save_system, save_profile_on_despawn_system, shutdown_profile_save_system};
+use crate::ecs::plugins::simulation::components::TICK_RATE_HZ;
+
+const DEFAULT_PROFILE_SAVE_INTERVAL_SECS: f64 = 30.0;
+
+// Persistence plugin: loads a player's last position, profile, health and inventory by account on
+// spawn, and saves it on despawn, periodically and on shutdown
+pub struct PersistencePlugin {
+    pub data_dir: String,
+    pub save_interval_secs: f64,
+}
+
+impl PersistencePlugin {
+    // Enabled when PLAYER_DATA_DIR is set; PLAYER_SAVE_INTERVAL_SECS controls the save period
+    pub fn from_env() -> Option<Self> {
+        let data_dir = std::env::var("PLAYER_DATA_DIR").ok().filter(|dir| !dir.is_empty())?;
+        let save_interval_secs = std::env::var("PLAYER_SAVE_INTERVAL_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_PROFILE_SAVE_INTERVAL_SECS);
+
+        Some(Self { data_dir, save_interval_secs })
+    }
+}
+
+impl Plugin for PersistencePlugin {
+    fn build(&self, app: &mut App) {
+        let store = match FilePlayerProfileStore::new(&self.data_dir) {
+            Ok(store) => store,
+            Err(e) => {
+                println!("❌ Failed to open player data directory {}: {}", self.data_dir, e);
+                return;
+            }
+        };
+        println!("📂 Player profiles stored in {}", self.data_dir);
+
+        app.insert_resource(PlayerProfileStorage {
+                store: Box::new(store),
+                save_interval_ticks: (self.save_interval_secs * TICK_RATE_HZ).round() as u64,
+            })
+            .add_systems(FixedUpdate, (
+                // The per-account profile is saved at logout, so it overrides the world snapshot;
+                // blocked spawns are fixed up after both have placed the player
+                load_player_profile_system
+                    .after(crate::ecs::systems::player_spawn_system)
+                    .after(crate::ecs::plugins::snapshot::systems::apply_restored_player_state_system)
+                    .before(crate::ecs::plugins::world_map::systems::relocate_blocked_spawns_system)
+                    .before(crate::ecs::plugins::zone::systems::restore_saved_zone_system)
+                    .before(crate::ecs::plugins::inventory::systems::attach_inventory_system),
+                save_profile_on_despawn_system.before(crate::ecs::systems::player_despawn_system),
+            ))
+            .add_systems(FixedLast, periodic_profile_save_system)
+            .add_systems(Last, shutdown_profile_save_system);
+    }
+}
@@ -0,0 +1,155 @@
+use bevy::ecs::query::ROQueryItem;
+use bevy::prelude::*;
+use crate::ecs::components::*;
+use crate::ecs::plugins::health::components::{Dead, Health, SavedHealth};
+use crate::ecs::plugins::inventory::components::{Inventory, SavedInventory};
+use crate::ecs::plugins::simulation::components::SimulationTick;
+use crate::ecs::plugins::zone::components::{SavedZone, ZoneId, Zones};
+use super::components::*;
+
+type SavedPlayerData = (
+    &'static Account,
+    &'static Position,
+    &'static CharacterProfile,
+    &'static ZoneId,
+    Option<&'static Inventory>,
+    &'static Health,
+    Option<&'static Dead>,
+);
+
+type SavedPlayerQuery<'w, 's> = Query<'w, 's, SavedPlayerData>;
+
+type ReturningAccountQuery<'w, 's> = Query<'w, 's, (
+    Entity,
+    &'static Player,
+    &'static Account,
+    &'static mut Position,
+    &'static mut CharacterProfile,
+    &'static mut Health,
+), Added<Account>>;
+
+// ============================================================================
+// PERSISTENCE HELPERS
+// ============================================================================
+
+fn save_profile(
+    storage: &PlayerProfileStorage,
+    zones: &Zones,
+    tick: u64,
+    (account, position, profile, zone, inventory, health, dead): ROQueryItem<SavedPlayerData>,
+) {
+    let stored = StoredPlayerProfile {
+        position: *position,
+        profile: *profile,
+        zone: Some(zones.name(*zone).to_string()),
+        inventory: inventory.map(|inventory| inventory.slots.clone()),
+        health: Some(SavedHealth::new(health, dead, tick)),
+    };
+    if let Err(e) = storage.store.save(&account.id, &stored) {
+        println!("❌ Failed to save profile for account {}: {}", account.id, e);
+    }
+}
+
+// ============================================================================
+// PERSISTENCE SYSTEMS
+// ============================================================================
+
+// Place returning players where they logged off, as they were and with what they were carrying
+pub fn load_player_profile_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    storage: Res<PlayerProfileStorage>,
+    game_config: Res<GameConfig>,
+    zones: Res<Zones>,
+    mut query: ReturningAccountQuery,
+) {
+    for (entity, player, account, mut position, mut profile, mut health) in query.iter_mut() {
+        match storage.store.load(&account.id) {
+            Ok(Some(stored)) => {
+                // The world may have shrunk since the profile was saved
+                position.x = stored.position.x.clamp(0.0, game_config.world_bounds.x);
+                position.y = stored.position.y.clamp(0.0, game_config.world_bounds.y);
+                *profile = stored.profile;
+                // Other zones have their own bounds, which the zone transfer clamps to
+                match zones.find_saved(stored.zone.as_deref()) {
+                    Some(zone) => {
+                        let saved_position = Vec2::new(stored.position.x, stored.position.y);
+                        commands.entity(entity).insert(SavedZone { zone, position: saved_position });
+                    }
+                    None => println!("❌ Account {} was saved in unknown zone {:?}", account.id, stored.zone),
+                }
+                if let Some(slots) = stored.inventory {
+                    commands.entity(entity).insert(SavedInventory(slots));
+                }
+                // The profile overrides the snapshot, which may have left the player dead
+                if let Some(saved) = stored.health {
+                    match saved.restore(&mut health, tick.0) {
+                        Some(dead) => commands.entity(entity).insert(dead),
+                        None => commands.entity(entity).remove::<Dead>(),
+                    };
+                }
+                println!("📂 Loaded profile for account {} (player {}) at ({:.1}, {:.1})", account.id, player.id, position.x, position.y);
+            }
+            Ok(None) => {
+                println!("📂 New account {} (player {})", account.id, player.id);
+            }
+            Err(e) => {
+                println!("❌ Failed to load profile for account {}: {}", account.id, e);
+            }
+        }
+    }
+}
+
+// Runs before the despawn system so the entity still exists
+pub fn save_profile_on_despawn_system(
+    tick: Res<SimulationTick>,
+    storage: Res<PlayerProfileStorage>,
+    zones: Res<Zones>,
+    mut despawn_events: EventReader<PlayerDespawnEvent>,
+    player_registry: Res<PlayerRegistry>,
+    query: SavedPlayerQuery,
+) {
+    for event in despawn_events.read() {
+        let Some(entity) = player_registry.get_player_entity(event.player_id) else {
+            continue;
+        };
+        if let Ok(player @ (account, ..)) = query.get(entity) {
+            save_profile(&storage, &zones, tick.0, player);
+            println!("💾 Saved profile for account {}", account.id);
+        }
+    }
+}
+
+pub fn periodic_profile_save_system(
+    tick: Res<SimulationTick>,
+    storage: Res<PlayerProfileStorage>,
+    zones: Res<Zones>,
+    query: SavedPlayerQuery,
+) {
+    if storage.save_interval_ticks == 0 || !tick.0.is_multiple_of(storage.save_interval_ticks) {
+        return;
+    }
+
+    for player in query.iter() {
+        save_profile(&storage, &zones, tick.0, player);
+    }
+}
+
+pub fn shutdown_profile_save_system(
+    mut exit_events: EventReader<AppExit>,
+    tick: Res<SimulationTick>,
+    storage: Res<PlayerProfileStorage>,
+    zones: Res<Zones>,
+    query: SavedPlayerQuery,
+) {
+    if exit_events.read().next().is_none() {
+        return;
+    }
+
+    let mut saved = 0;
+    for player in query.iter() {
+        save_profile(&storage, &zones, tick.0, player);
+        saved += 1;
+    }
+    println!("💾 Saved {} player profiles on shutdown", saved);
+}
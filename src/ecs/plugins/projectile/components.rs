@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use crate::ecs::components::{Position, Velocity};
+use crate::ecs::plugins::network::components::{NetworkEntityKind, NetworkExtrapolated, NetworkedEntityBundle};
+use crate::ecs::plugins::zone::components::ZoneId;
+
+// ============================================================================
+// PROJECTILE DATA
+// ============================================================================
+
+// How an ability launches its projectile; it flies for the ability's range
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct ProjectileSpec {
+    pub speed: f32,
+    pub hit_radius: f32,
+}
+
+// ============================================================================
+// PROJECTILE COMPONENTS
+// ============================================================================
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Projectile {
+    pub owner: Entity,
+    // Player ID of the owner, or None for an NPC; decides who the projectile can hit
+    pub owner_player: Option<u32>,
+    pub damage: f32,
+    pub hit_radius: f32,
+    pub expires_tick: u64,
+    // How far behind the owner's view was at launch; hits are checked that far back
+    pub rewind_ticks: u64,
+}
+
+// No Collider, friction or desired velocity: movement_system carries it in a straight line
+#[derive(Bundle)]
+pub struct ProjectileBundle {
+    pub projectile: Projectile,
+    pub position: Position,
+    pub velocity: Velocity,
+    pub zone: ZoneId,
+    pub networked: NetworkedEntityBundle,
+    pub extrapolated: NetworkExtrapolated,
+}
+
+impl ProjectileBundle {
+    pub fn new(projectile: Projectile, origin: Vec2, velocity: Vec2, zone: ZoneId, network_id: u32) -> Self {
+        Self {
+            projectile,
+            position: Position { x: origin.x, y: origin.y },
+            velocity: Velocity { x: velocity.x, y: velocity.y },
+            zone,
+            networked: NetworkedEntityBundle::new(network_id, NetworkEntityKind::Projectile),
+            extrapolated: NetworkExtrapolated,
+        }
+    }
+}
+
+// ============================================================================
+// PROJECTILE MESSAGE KEYS
+// ============================================================================
+
+pub const HIT_TARGET_KEY: &str = "tg";
+
+// ============================================================================
+// PROJECTILE EVENTS
+// ============================================================================
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ProjectileHitEvent {
+    pub owner: Entity,
+    pub target: Entity,
+    pub damage: f32,
+}
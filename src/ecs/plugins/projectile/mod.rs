@@ -0,0 +1,20 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::ProjectileHitEvent;
+use systems::{projectile_damage_system, projectile_hit_system};
+
+// Projectile plugin: short-lived straight-line projectiles with swept hit detection
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ProjectileHitEvent>()
+            // Projectiles launched this tick are swept from their first step, and hits are
+            // applied with the rest of this tick's damage
+            .add_systems(FixedUpdate, (projectile_hit_system, projectile_damage_system).chain()
+                .after(crate::ecs::plugins::ability::systems::ability_resolve_system)
+                .before(crate::ecs::plugins::health::systems::apply_damage_system));
+    }
+}
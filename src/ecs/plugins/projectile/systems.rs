@@ -0,0 +1,177 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::ecs::components::*;
+use crate::ecs::plugins::collision::components::Collider;
+use crate::ecs::plugins::health::components::{can_damage, DamageEvent, Health};
+use crate::ecs::plugins::lag_compensation::components::{rewound_position, PositionHistory};
+use crate::ecs::plugins::network::components::{
+    EntityUpdate, NetworkId, NetworkMessage, NetworkUpdates, ViewRangeTracker, POSITION_KEY, PROJECTILE_END_TYPE,
+};
+use crate::ecs::plugins::party::components::Parties;
+use crate::ecs::plugins::simulation::components::SimulationTick;
+use crate::ecs::plugins::world_map::components::WorldMap;
+use crate::ecs::plugins::zone::components::{ZoneId, Zones, MAIN_ZONE};
+use super::components::*;
+
+type ProjectileTargetQuery<'w, 's> = Query<'w, 's, (
+    Entity,
+    Option<&'static Player>,
+    &'static Position,
+    &'static ZoneId,
+    &'static Collider,
+    &'static Health,
+    Option<&'static NetworkId>,
+    Option<&'static PositionHistory>,
+)>;
+
+// ============================================================================
+// PROJECTILE HELPERS
+// ============================================================================
+
+// Fraction along the segment start..end where a circle at `center` with radius `reach`
+// is first touched (0 if the segment starts inside it)
+fn sweep_hit(start: Vec2, end: Vec2, center: Vec2, reach: f32) -> Option<f32> {
+    let segment = end - start;
+    let offset = start - center;
+    let c = offset.length_squared() - reach * reach;
+    if c <= 0.0 {
+        return Some(0.0);
+    }
+    let a = segment.length_squared();
+    let b = 2.0 * offset.dot(segment);
+    let discriminant = b * b - 4.0 * a * c;
+    if a == 0.0 || discriminant < 0.0 {
+        return None;
+    }
+    let t = (-b - discriminant.sqrt()) / (2.0 * a);
+    (0.0..=1.0).contains(&t).then_some(t)
+}
+
+fn outside_zone(point: Vec2, bounds: Vec2) -> bool {
+    point.x < 0.0 || point.y < 0.0 || point.x > bounds.x || point.y > bounds.y
+}
+
+// ============================================================================
+// PROJECTILE SYSTEMS
+// ============================================================================
+
+// Runs before movement: sweeps the path each projectile is about to travel this tick
+// and ends it on the first body or wall in the way, the zone edge or the end of its
+// lifetime, so its damage lands in this tick's health pass. Clients only hear about
+// a projectile when it appears and when it ends.
+#[allow(clippy::too_many_arguments)]
+pub fn projectile_hit_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    time: Res<Time<Fixed>>,
+    game_config: Res<GameConfig>,
+    zones: Res<Zones>,
+    map: Option<Res<WorldMap>>,
+    parties: Res<Parties>,
+    mut network_updates: ResMut<NetworkUpdates>,
+    mut hit_events: EventWriter<ProjectileHitEvent>,
+    projectiles: Query<(Entity, &Projectile, &Position, &Velocity, &ZoneId, &NetworkId, &ViewRangeTracker)>,
+    targets: ProjectileTargetQuery,
+) {
+    let dt = time.timestep().as_secs_f32();
+
+    for (entity, projectile, position, velocity, zone, network_id, view_tracker) in projectiles.iter() {
+        let start = Vec2::new(position.x, position.y);
+        let end = start + Vec2::new(velocity.x, velocity.y) * dt;
+        let view_tick = (projectile.rewind_ticks > 0).then(|| tick.0.saturating_sub(projectile.rewind_ticks));
+
+        // The map only covers the main zone
+        let wall = (*zone == MAIN_ZONE)
+            .then(|| map.as_deref().and_then(|map| map.segment_blocked_at(start, end)))
+            .flatten();
+
+        // Projectiles pass through bodies their owner can't damage, and stop at walls
+        let hit = targets.iter()
+            .filter(|(target, target_player, _, target_zone, _, health, _, _)| {
+                *target != projectile.owner
+                    && *target_zone == zone
+                    && !health.is_dead()
+                    && can_damage(&parties, projectile.owner_player, target_player.map(|player| player.id))
+            })
+            .filter_map(|(target, _, target_position, _, collider, _, target_network_id, history)| {
+                let center = rewound_position(history, target_position, view_tick);
+                sweep_hit(start, end, center, projectile.hit_radius + collider.radius)
+                    .filter(|t| wall.is_none_or(|wall| *t <= wall))
+                    .map(|t| (t, target, target_network_id))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+
+        let (stop_at, target_network_id) = match (hit, wall) {
+            (Some((t, target, target_network_id)), _) => {
+                hit_events.send(ProjectileHitEvent { owner: projectile.owner, target, damage: projectile.damage });
+                (start + (end - start) * t, target_network_id.map(|id| id.0))
+            }
+            (None, Some(wall)) => (start + (end - start) * wall, None),
+            (None, None) if tick.0 >= projectile.expires_tick || outside_zone(end, zones.bounds(*zone, &game_config)) => (end, None),
+            (None, None) => continue,
+        };
+
+        commands.entity(entity).despawn();
+
+        let mut components = HashMap::new();
+        components.insert(POSITION_KEY.to_string(), serde_json::json!([stop_at.x.round(), stop_at.y.round()]));
+        if let Some(target_network_id) = target_network_id {
+            components.insert(HIT_TARGET_KEY.to_string(), serde_json::Value::from(target_network_id));
+        }
+        let message = NetworkMessage {
+            message_type: PROJECTILE_END_TYPE.to_string(),
+            entity_updates: vec![EntityUpdate { network_id: network_id.0, components }],
+            tick: None,
+        };
+        for player_id in &view_tracker.players_in_view {
+            network_updates.player_messages.entry(*player_id).or_default().push(message.clone());
+        }
+    }
+}
+
+pub fn projectile_damage_system(
+    mut hit_events: EventReader<ProjectileHitEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    for hit in hit_events.read() {
+        damage_events.send(DamageEvent { target: hit.target, amount: hit.damage, source: Some(hit.owner) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hits_where_the_path_first_touches_the_body() {
+        let t = sweep_hit(Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(6.0, 0.0), 1.0);
+        assert_eq!(t, Some(0.5));
+    }
+
+    #[test]
+    fn misses_bodies_beside_behind_or_beyond_the_path() {
+        let (start, end) = (Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0));
+        assert_eq!(sweep_hit(start, end, Vec2::new(5.0, 3.0), 1.0), None);
+        assert_eq!(sweep_hit(start, end, Vec2::new(-3.0, 0.0), 1.0), None);
+        assert_eq!(sweep_hit(start, end, Vec2::new(13.0, 0.0), 1.0), None);
+    }
+
+    #[test]
+    fn starting_inside_a_body_hits_at_once() {
+        let t = sweep_hit(Vec2::new(5.5, 0.0), Vec2::new(10.0, 0.0), Vec2::new(5.0, 0.0), 1.0);
+        assert_eq!(t, Some(0.0));
+    }
+
+    #[test]
+    fn grazing_the_edge_counts_as_a_hit() {
+        let t = sweep_hit(Vec2::new(0.0, 1.0), Vec2::new(2.0, 1.0), Vec2::new(1.0, 0.0), 1.0);
+        assert_eq!(t, Some(0.5));
+    }
+
+    #[test]
+    fn a_projectile_that_does_not_move_only_hits_what_it_is_inside() {
+        let point = Vec2::new(3.0, 4.0);
+        assert_eq!(sweep_hit(point, point, Vec2::new(3.0, 4.5), 1.0), Some(0.0));
+        assert_eq!(sweep_hit(point, point, Vec2::new(3.0, 6.0), 1.0), None);
+    }
+}
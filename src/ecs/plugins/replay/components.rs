@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use crate::ecs::components::{InputCommand, Position};
+
+// ============================================================================
+// REPLAY FILE FORMAT
+// ============================================================================
+
+// A replay file is newline-delimited JSON: one header line followed by one record
+// per recorded event, all using short keys to keep the file compact.
+
+pub const REPLAY_FORMAT_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayHeader {
+    #[serde(rename = "v")]
+    pub version: u32,
+    #[serde(rename = "s")]
+    pub seed: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayRecord {
+    #[serde(rename = "t")]
+    pub tick: u64,
+    #[serde(rename = "e")]
+    pub event: ReplayEvent,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ReplayEvent {
+    #[serde(rename = "i")]
+    Input { player_id: u32, command: InputCommand },
+    #[serde(rename = "ps")]
+    PlayerSpawn {
+        player_id: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        account: Option<String>,
+    },
+    #[serde(rename = "pd")]
+    PlayerDespawn { player_id: u32 },
+    #[serde(rename = "cs")]
+    CharacterSpawn {
+        character_id: u32,
+        position: Option<Position>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        archetype: Option<String>,
+        #[serde(default)]
+        networked: bool,
+    },
+    #[serde(rename = "h")]
+    StateHash { hash: u64 },
+}
+
+#[derive(Clone, Debug)]
+pub struct ReplayLog {
+    pub header: ReplayHeader,
+    pub records: Vec<ReplayRecord>,
+}
+
+impl ReplayLog {
+    pub fn load(path: &str) -> std::io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut lines = reader.lines();
+
+        let header_line = lines.next().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, "replay file is empty")
+        })??;
+        let header: ReplayHeader = serde_json::from_str(&header_line)?;
+
+        let mut records = Vec::new();
+        for line in lines {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            records.push(serde_json::from_str(&line)?);
+        }
+
+        Ok(Self { header, records })
+    }
+}
+
+// ============================================================================
+// REPLAY RESOURCES
+// ============================================================================
+
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    pub writer: BufWriter<File>,
+    pub hash_interval: u64,
+}
+
+impl ReplayRecorder {
+    pub fn create(path: &str, seed: u64, hash_interval: u64) -> std::io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let header = ReplayHeader { version: REPLAY_FORMAT_VERSION, seed };
+        writeln!(writer, "{}", serde_json::to_string(&header)?)?;
+        Ok(Self { writer, hash_interval })
+    }
+
+    pub fn write(&mut self, tick: u64, event: ReplayEvent) {
+        let record = ReplayRecord { tick, event };
+        if let Ok(line) = serde_json::to_string(&record)
+            && let Err(e) = writeln!(self.writer, "{}", line)
+        {
+            println!("❌ Failed to write replay record: {}", e);
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub pending: VecDeque<ReplayRecord>,
+    pub expected_hashes: HashMap<u64, u64>,
+    pub verify: bool,
+    pub hashes_checked: u32,
+    pub divergences: u32,
+}
+
+impl ReplayPlayback {
+    pub fn new(log: ReplayLog, verify: bool) -> Self {
+        Self {
+            pending: log.records.into(),
+            expected_hashes: HashMap::new(),
+            verify,
+            hashes_checked: 0,
+            divergences: 0,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::{ReplayLog, ReplayPlayback, ReplayRecorder};
+use systems::{
+    discard_network_updates_system, record_session_events_system, record_state_hash_system,
+    replay_feed_system, replay_finish_system, replay_verify_system,
+};
+use crate::ecs::plugins::simulation::SimulationPlugin;
+use crate::ecs::plugins::simulation::components::SimulationConfig;
+use crate::ecs::plugins::simulation::systems::advance_simulation_tick_system;
+
+const DEFAULT_HASH_INTERVAL: u64 = 50;
+
+pub enum ReplayMode {
+    Record { path: String, hash_interval: u64 },
+    Playback { log: ReplayLog, verify: bool },
+}
+
+// Replay plugin: records every session event to a file, or feeds a recorded file
+// back into a headless app instead of the WebSocket layer
+pub struct ReplayPlugin {
+    pub mode: ReplayMode,
+}
+
+impl ReplayPlugin {
+    // Reads REPLAY_RECORD / REPLAY_FILE (plus REPLAY_HASH_INTERVAL and REPLAY_VERIFY)
+    pub fn from_env() -> Option<Self> {
+        if let Ok(path) = std::env::var("REPLAY_FILE") {
+            let verify = std::env::var("REPLAY_VERIFY").map(|value| value != "0").unwrap_or(true);
+            return match ReplayLog::load(&path) {
+                Ok(log) => {
+                    println!("📼 Loaded replay {} ({} records)", path, log.records.len());
+                    Some(Self { mode: ReplayMode::Playback { log, verify } })
+                }
+                Err(e) => {
+                    println!("❌ Failed to load replay {}: {}", path, e);
+                    None
+                }
+            };
+        }
+
+        std::env::var("REPLAY_RECORD").ok().map(|path| {
+            let hash_interval = std::env::var("REPLAY_HASH_INTERVAL")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(DEFAULT_HASH_INTERVAL);
+            Self { mode: ReplayMode::Record { path, hash_interval } }
+        })
+    }
+
+    pub fn is_playback(&self) -> bool {
+        matches!(self.mode, ReplayMode::Playback { .. })
+    }
+
+    // Recording and playback both need the deterministic simulation; playback also
+    // reuses the seed the session was recorded with
+    pub fn configure_simulation(&self, simulation: &mut SimulationPlugin) {
+        simulation.deterministic = true;
+        if let ReplayMode::Playback { log, .. } = &self.mode {
+            simulation.seed = Some(log.header.seed);
+        }
+    }
+}
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        match &self.mode {
+            ReplayMode::Record { path, hash_interval } => {
+                let seed = app.world().resource::<SimulationConfig>().seed;
+                match ReplayRecorder::create(path, seed, *hash_interval) {
+                    Ok(recorder) => {
+                        println!("⏺️ Recording session to {}", path);
+                        app.insert_resource(recorder)
+                            .add_systems(FixedUpdate, record_session_events_system)
+                            .add_systems(FixedLast, record_state_hash_system);
+                    }
+                    Err(e) => println!("❌ Failed to create replay file {}: {}", path, e),
+                }
+            }
+            ReplayMode::Playback { log, verify } => {
+                app.insert_resource(ReplayPlayback::new(log.clone(), *verify))
+                    .add_systems(FixedFirst, replay_feed_system.after(advance_simulation_tick_system))
+                    .add_systems(FixedLast, (replay_verify_system, replay_finish_system).chain())
+                    .add_systems(Last, discard_network_updates_system);
+            }
+        }
+    }
+}
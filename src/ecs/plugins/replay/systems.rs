@@ -0,0 +1,141 @@
+use bevy::prelude::*;
+use crate::ecs::components::*;
+use crate::ecs::plugins::network::components::NetworkUpdates;
+use crate::ecs::plugins::simulation::components::SimulationTick;
+use crate::ecs::plugins::simulation::systems::{compute_state_hash, SimulationStateQuery};
+use super::components::*;
+
+// ============================================================================
+// RECORDING SYSTEMS
+// ============================================================================
+
+pub fn record_session_events_system(
+    tick: Res<SimulationTick>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut input_events: EventReader<InputCommandEvent>,
+    mut spawn_events: EventReader<PlayerSpawnEvent>,
+    mut despawn_events: EventReader<PlayerDespawnEvent>,
+    mut character_spawn_events: EventReader<CharacterSpawnEvent>,
+) {
+    for event in spawn_events.read() {
+        recorder.write(tick.0, ReplayEvent::PlayerSpawn {
+            player_id: event.player_id,
+            account: event.account.clone(),
+        });
+    }
+    for event in despawn_events.read() {
+        recorder.write(tick.0, ReplayEvent::PlayerDespawn { player_id: event.player_id });
+    }
+    for event in character_spawn_events.read() {
+        recorder.write(tick.0, ReplayEvent::CharacterSpawn {
+            character_id: event.character_id,
+            position: event.position,
+            archetype: event.archetype.clone(),
+            networked: event.networked,
+        });
+    }
+    for event in input_events.read() {
+        recorder.write(tick.0, ReplayEvent::Input {
+            player_id: event.player_id,
+            command: event.command.clone(),
+        });
+    }
+}
+
+pub fn record_state_hash_system(
+    tick: Res<SimulationTick>,
+    mut recorder: ResMut<ReplayRecorder>,
+    query: SimulationStateQuery,
+) {
+    if recorder.hash_interval > 0 && tick.0.is_multiple_of(recorder.hash_interval) {
+        let hash = compute_state_hash(&query);
+        recorder.write(tick.0, ReplayEvent::StateHash { hash });
+    }
+
+    // Flush every tick so a crash loses at most one tick of input
+    if let Err(e) = std::io::Write::flush(&mut recorder.writer) {
+        println!("❌ Failed to flush replay file: {}", e);
+    }
+}
+
+// ============================================================================
+// PLAYBACK SYSTEMS
+// ============================================================================
+
+// Feeds recorded events into the tick they were originally consumed on
+pub fn replay_feed_system(
+    tick: Res<SimulationTick>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut input_events: EventWriter<InputCommandEvent>,
+    mut spawn_events: EventWriter<PlayerSpawnEvent>,
+    mut despawn_events: EventWriter<PlayerDespawnEvent>,
+    mut character_spawn_events: EventWriter<CharacterSpawnEvent>,
+) {
+    while playback.pending.front().is_some_and(|record| record.tick <= tick.0) {
+        let Some(record) = playback.pending.pop_front() else {
+            break;
+        };
+
+        match record.event {
+            ReplayEvent::Input { player_id, command } => {
+                input_events.send(InputCommandEvent { player_id, command });
+            }
+            ReplayEvent::PlayerSpawn { player_id, account } => {
+                spawn_events.send(PlayerSpawnEvent { player_id, account });
+            }
+            ReplayEvent::PlayerDespawn { player_id } => {
+                despawn_events.send(PlayerDespawnEvent { player_id });
+            }
+            ReplayEvent::CharacterSpawn { character_id, position, archetype, networked } => {
+                character_spawn_events.send(CharacterSpawnEvent { character_id, position, archetype, networked });
+            }
+            ReplayEvent::StateHash { hash } => {
+                playback.expected_hashes.insert(record.tick, hash);
+            }
+        }
+    }
+}
+
+pub fn replay_verify_system(
+    tick: Res<SimulationTick>,
+    mut playback: ResMut<ReplayPlayback>,
+    query: SimulationStateQuery,
+) {
+    let Some(expected) = playback.expected_hashes.remove(&tick.0) else {
+        return;
+    };
+    if !playback.verify {
+        return;
+    }
+
+    let actual = compute_state_hash(&query);
+    playback.hashes_checked += 1;
+    if actual != expected {
+        playback.divergences += 1;
+        println!("❌ Replay diverged at tick {}: expected {:016x}, got {:016x}", tick.0, expected, actual);
+    }
+}
+
+pub fn replay_finish_system(
+    tick: Res<SimulationTick>,
+    playback: Res<ReplayPlayback>,
+    mut exit_events: EventWriter<AppExit>,
+) {
+    if !playback.pending.is_empty() || !playback.expected_hashes.is_empty() {
+        return;
+    }
+
+    if playback.divergences == 0 {
+        println!("✅ Replay finished at tick {} ({} state hashes matched)", tick.0, playback.hashes_checked);
+        exit_events.send(AppExit::Success);
+    } else {
+        println!("❌ Replay finished at tick {} with {} divergences", tick.0, playback.divergences);
+        exit_events.send(AppExit::error());
+    }
+}
+
+// No clients are connected in playback, so outgoing messages are dropped
+pub fn discard_network_updates_system(mut network_updates: ResMut<NetworkUpdates>) {
+    network_updates.messages.clear();
+    network_updates.player_messages.clear();
+}
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+use crossbeam_channel::{Receiver, Sender};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::ecs::components::{CharacterProfile, Position, Velocity};
+use crate::ecs::plugins::inventory::components::ItemStack;
+use crate::ecs::plugins::network::components::NetworkEntityKind;
+
+// Ghosts from a shard that has gone quiet this long are removed
+pub const GHOST_TIMEOUT_TICKS: u64 = 20;
+
+// ============================================================================
+// SHARD DATA
+// ============================================================================
+
+// One server process and the strip of the main zone it owns, min_x <= x < max_x
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShardDefinition {
+    pub name: String,
+    pub min_x: f32,
+    pub max_x: f32,
+    // Where clients connect to this shard
+    pub client_url: String,
+    // Where other shards send handoffs to this shard
+    pub handoff_addr: String,
+}
+
+impl ShardDefinition {
+    pub fn contains(&self, x: f32) -> bool {
+        x >= self.min_x && x < self.max_x
+    }
+
+    pub fn distance_to(&self, x: f32) -> f32 {
+        (self.min_x - x).max(x - self.max_x).max(0.0)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ShardMapFile {
+    pub shards: Vec<ShardDefinition>,
+}
+
+// ============================================================================
+// HANDOFF PROTOCOL
+// ============================================================================
+
+// Everything a shard needs to take over a player. The client reconnects to the new
+// shard with the token, which must also present the same account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerHandoff {
+    pub token: String,
+    pub account: Option<String>,
+    pub position: Position,
+    pub velocity: Velocity,
+    pub profile: CharacterProfile,
+    pub health: f32,
+    pub inventory: Vec<Option<ItemStack>>,
+}
+
+// A networked entity near the border, as its owning shard sees it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GhostState {
+    pub network_id: u32,
+    pub kind: NetworkEntityKind,
+    pub position: Position,
+    pub components: HashMap<String, serde_json::Value>,
+}
+
+// Newline-delimited JSON between shards, one SignedShardMessage per line: the sender
+// writes a Handoff line and the receiver answers with Accepted once it is holding the
+// player. Ghosts lines are streamed every tick over a long-lived connection and list
+// every entity the sender has within view range of the receiver's region.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ShardMessage {
+    Handoff(PlayerHandoff),
+    Accepted { token: String },
+    Ghosts { shard: String, entities: Vec<GhostState> },
+}
+
+// How every ShardMessage travels: its JSON and the hex HMAC-SHA256 of that JSON under
+// SHARD_SECRET. Lines that fail the check are dropped along with their connection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedShardMessage {
+    pub message: String,
+    pub sig: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct HandoffResult {
+    pub token: String,
+    pub accepted: bool,
+}
+
+// ============================================================================
+// SHARD COMPONENTS
+// ============================================================================
+
+// Player waiting for the neighbouring shard to accept them
+#[derive(Component, Debug, Clone)]
+pub struct PendingHandoff {
+    pub token: String,
+    pub shard: usize,
+}
+
+// Read-only replica of an entity owned by another shard, tracked in GhostRegistry.
+// It has no simulation components, so only the network systems ever see it.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Ghost;
+
+// ============================================================================
+// SHARD RESOURCES
+// ============================================================================
+
+#[derive(Resource, Debug, Clone)]
+pub struct ShardMap {
+    pub shards: Vec<ShardDefinition>,
+    // Index of this process in `shards`
+    pub this: usize,
+}
+
+impl ShardMap {
+    pub fn local(&self) -> &ShardDefinition {
+        &self.shards[self.this]
+    }
+
+    pub fn owner_of(&self, x: f32) -> Option<usize> {
+        self.shards.iter().position(|shard| shard.contains(x))
+    }
+}
+
+// Channels to the handoff thread, which does all of the blocking TCP work
+#[derive(Resource)]
+pub struct ShardLink {
+    pub outgoing: Sender<(String, PlayerHandoff)>,
+    pub results: Receiver<HandoffResult>,
+    pub incoming: Receiver<PlayerHandoff>,
+    pub ghosts_outgoing: Sender<(String, ShardMessage)>,
+    pub ghosts_incoming: Receiver<(String, Vec<GhostState>)>,
+}
+
+// Handoffs accepted from other shards, waiting for their player to reconnect
+#[derive(Resource, Default)]
+pub struct HandoffArrivals {
+    pub pending: HashMap<String, (PlayerHandoff, u64)>,
+}
+
+// Local ghost entities by (shard, network ID on that shard)
+#[derive(Resource, Default)]
+pub struct GhostRegistry {
+    pub ghosts: HashMap<(usize, u32), Entity>,
+    pub last_heard: HashMap<usize, u64>,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct ShardConfig {
+    pub arrival_timeout_ticks: u64,
+    // Entities this close to another shard's region are mirrored to it
+    pub ghost_margin: f32,
+}
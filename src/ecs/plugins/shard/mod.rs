@@ -0,0 +1,109 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::{GhostRegistry, HandoffArrivals, ShardConfig, ShardLink, ShardMap, ShardMapFile};
+use systems::{
+    apply_ghosts_system, apply_handoff_system, claim_region_npcs_system, handoff_result_system, receive_handoffs_system,
+    send_ghosts_system, shard_boundary_system, start_handoff_thread,
+};
+use crate::ecs::components::ViewDistance;
+use crate::ecs::plugins::simulation::components::TICK_RATE_HZ;
+
+const DEFAULT_HANDOFF_ARRIVAL_TIMEOUT_SECS: f64 = 30.0;
+
+// Shard plugin: splits the main zone between server processes by x. Players who
+// cross into a neighbour's region are handed to it over TCP and their client is
+// redirected there. Entities within view range of a border are mirrored to the
+// neighbour as read-only ghosts so players on either side can see across it.
+pub struct ShardPlugin {
+    pub map_path: String,
+    pub shard_name: String,
+    // Shared by every shard; all traffic between them is signed with it
+    pub secret: Option<String>,
+    pub arrival_timeout_secs: f64,
+}
+
+impl ShardPlugin {
+    // Enabled when SHARD_MAP and SHARD_NAME are set, and requires SHARD_SECRET;
+    // HANDOFF_ARRIVAL_TIMEOUT_SECS is how long an accepted player has to reconnect
+    pub fn from_env() -> Option<Self> {
+        let map_path = std::env::var("SHARD_MAP").ok().filter(|path| !path.is_empty())?;
+        let shard_name = std::env::var("SHARD_NAME").ok().filter(|name| !name.is_empty())?;
+        let secret = std::env::var("SHARD_SECRET").ok().filter(|secret| !secret.is_empty());
+        let arrival_timeout_secs = std::env::var("HANDOFF_ARRIVAL_TIMEOUT_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_HANDOFF_ARRIVAL_TIMEOUT_SECS);
+
+        Some(Self { map_path, shard_name, secret, arrival_timeout_secs })
+    }
+}
+
+impl Plugin for ShardPlugin {
+    fn build(&self, app: &mut App) {
+        // Handoffs carry whole players, so an unauthenticated link is never opened
+        let Some(secret) = self.secret.clone() else {
+            println!("❌ SHARD_SECRET is not set (running unsharded)");
+            return;
+        };
+        let data = std::fs::read_to_string(&self.map_path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| serde_json::from_str::<ShardMapFile>(&data).map_err(|e| e.to_string()));
+
+        let shards = match data {
+            Ok(data) => data.shards,
+            Err(e) => {
+                println!("❌ Failed to load shard map {}: {} (running unsharded)", self.map_path, e);
+                return;
+            }
+        };
+        let Some(this) = shards.iter().position(|shard| shard.name == self.shard_name) else {
+            println!("❌ Shard '{}' is not in {} (running unsharded)", self.shard_name, self.map_path);
+            return;
+        };
+        let shard_map = ShardMap { shards, this };
+        let local = shard_map.local();
+        println!("🧩 Running shard '{}' owning x {}..{} ({} shards)", local.name, local.min_x, local.max_x, shard_map.shards.len());
+
+        let (outgoing_tx, outgoing_rx) = crossbeam_channel::unbounded();
+        let (results_tx, results_rx) = crossbeam_channel::unbounded();
+        let (incoming_tx, incoming_rx) = crossbeam_channel::unbounded();
+        // Ghost states are only worth sending while fresh, so a stalled link drops them
+        let (ghosts_outgoing_tx, ghosts_outgoing_rx) = crossbeam_channel::bounded(shard_map.shards.len() * 4);
+        let (ghosts_incoming_tx, ghosts_incoming_rx) = crossbeam_channel::unbounded();
+        start_handoff_thread(
+            local.handoff_addr.clone(), secret, outgoing_rx, results_tx, incoming_tx, ghosts_outgoing_rx, ghosts_incoming_tx,
+        );
+
+        app.insert_resource(shard_map)
+            .insert_resource(ShardLink {
+                outgoing: outgoing_tx,
+                results: results_rx,
+                incoming: incoming_rx,
+                ghosts_outgoing: ghosts_outgoing_tx,
+                ghosts_incoming: ghosts_incoming_rx,
+            })
+            .insert_resource(HandoffArrivals::default())
+            .insert_resource(GhostRegistry::default())
+            .insert_resource(ShardConfig {
+                arrival_timeout_ticks: (self.arrival_timeout_secs * TICK_RATE_HZ).round() as u64,
+                // Far enough for anything a default view range still holds
+                ghost_margin: ViewDistance::default().exit_range(),
+            })
+            .add_systems(Startup, claim_region_npcs_system)
+            .add_systems(FixedUpdate, (
+                (shard_boundary_system, handoff_result_system).chain()
+                    .after(crate::ecs::plugins::zone::systems::zone_transfer_system)
+                    .before(crate::ecs::plugins::network::systems::detect_position_changes_system),
+                receive_handoffs_system.before(apply_handoff_system),
+                apply_handoff_system
+                    .after(crate::ecs::plugins::inventory::systems::attach_inventory_system)
+                    .after(crate::ecs::plugins::persistence::systems::load_player_profile_system)
+                    .after(crate::ecs::plugins::snapshot::systems::apply_restored_player_state_system)
+                    .before(crate::ecs::plugins::inventory::systems::inventory_sync_system),
+                apply_ghosts_system.before(crate::ecs::plugins::network::systems::detect_position_changes_system),
+                send_ghosts_system.after(crate::ecs::plugins::network::systems::build_delta_updates_system),
+            ));
+    }
+}
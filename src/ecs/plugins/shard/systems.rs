@@ -0,0 +1,454 @@
+use bevy::prelude::*;
+use crossbeam_channel::{Receiver, Sender};
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use crate::ecs::components::*;
+use crate::ecs::plugins::ai::components::NpcSpawnList;
+use crate::ecs::plugins::health::components::{Dead, Health};
+use crate::ecs::plugins::inventory::components::Inventory;
+use crate::ecs::plugins::network::components::{
+    sign_message, verify_message, NetworkDirty, NetworkEntityKind, NetworkId, NetworkIdAllocator, NetworkSnapshot,
+    NetworkedEntityBundle,
+};
+use crate::ecs::plugins::simulation::components::SimulationTick;
+use crate::ecs::plugins::zone::components::{ZoneId, MAIN_ZONE};
+use super::components::*;
+
+// Players free to cross a shard boundary
+type CrossingPlayerQuery<'w, 's> = Query<'w, 's, (
+    Entity,
+    &'static Player,
+    &'static Position,
+    &'static mut Velocity,
+    &'static mut DesiredVelocity,
+    &'static CharacterProfile,
+    &'static Health,
+    &'static ZoneId,
+    Option<&'static Account>,
+    Option<&'static Inventory>,
+), (Without<PendingHandoff>, Without<Dead>)>;
+
+type ArrivingPlayerQuery<'w, 's> = Query<'w, 's, (
+    Option<&'static Account>,
+    &'static mut Position,
+    &'static mut Velocity,
+    &'static mut CharacterProfile,
+    &'static mut Health,
+    Option<&'static mut Inventory>,
+)>;
+
+const HANDOFF_IO_TIMEOUT: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// HANDOFF TRANSPORT
+// ============================================================================
+
+// Serializes and signs a message as one line
+fn encode_message(secret: &str, message: &ShardMessage) -> Option<String> {
+    let message = serde_json::to_string(message).ok()?;
+    let sig = sign_message(secret, message.as_bytes());
+    let mut line = serde_json::to_string(&SignedShardMessage { message, sig }).ok()?;
+    line.push('\n');
+    Some(line)
+}
+
+// The message on a line, if it was signed with our secret
+fn decode_message(secret: &str, line: &str) -> Option<ShardMessage> {
+    let signed: SignedShardMessage = serde_json::from_str(line).ok()?;
+    if !verify_message(secret, signed.message.as_bytes(), &signed.sig) {
+        return None;
+    }
+    serde_json::from_str(&signed.message).ok()
+}
+
+// Accepts handoffs and ghosts from other shards and sends ours, each connection on its own thread
+pub fn start_handoff_thread(
+    listen_addr: String,
+    secret: String,
+    outgoing: Receiver<(String, PlayerHandoff)>,
+    results: Sender<HandoffResult>,
+    incoming: Sender<PlayerHandoff>,
+    ghosts_outgoing: Receiver<(String, ShardMessage)>,
+    ghosts_incoming: Sender<(String, Vec<GhostState>)>,
+) {
+    let listener = match TcpListener::bind(&listen_addr) {
+        Ok(listener) => listener,
+        Err(e) => {
+            println!("❌ Failed to listen for shard handoffs on {}: {}", listen_addr, e);
+            return;
+        }
+    };
+    println!("🔀 Listening for shard handoffs on {}", listen_addr);
+    let secret: Arc<str> = secret.into();
+
+    let listener_secret = secret.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let incoming = incoming.clone();
+            let ghosts_incoming = ghosts_incoming.clone();
+            let secret = listener_secret.clone();
+            thread::spawn(move || receive_shard_messages(stream, &secret, incoming, ghosts_incoming));
+        }
+    });
+
+    let handoff_secret = secret.clone();
+    thread::spawn(move || {
+        while let Ok((addr, handoff)) = outgoing.recv() {
+            let results = results.clone();
+            let secret = handoff_secret.clone();
+            thread::spawn(move || {
+                let token = handoff.token.clone();
+                let accepted = match send_handoff(&addr, &secret, handoff) {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        println!("❌ Shard handoff to {} failed: {}", addr, e);
+                        false
+                    }
+                };
+                let _ = results.send(HandoffResult { token, accepted });
+            });
+        }
+    });
+
+    thread::spawn(move || send_ghosts(ghosts_outgoing, &secret));
+}
+
+fn connect(addr: &str) -> std::io::Result<TcpStream> {
+    let socket_addr = addr.to_socket_addrs()?
+        .next()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no address"))?;
+    let stream = TcpStream::connect_timeout(&socket_addr, HANDOFF_IO_TIMEOUT)?;
+    stream.set_write_timeout(Some(HANDOFF_IO_TIMEOUT))?;
+    Ok(stream)
+}
+
+fn send_handoff(addr: &str, secret: &str, handoff: PlayerHandoff) -> std::io::Result<bool> {
+    let token = handoff.token.clone();
+    let line = encode_message(secret, &ShardMessage::Handoff(handoff))
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "unencodable handoff"))?;
+    let mut stream = connect(addr)?;
+    stream.set_read_timeout(Some(HANDOFF_IO_TIMEOUT))?;
+    stream.write_all(line.as_bytes())?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    Ok(matches!(
+        decode_message(secret, &reply),
+        Some(ShardMessage::Accepted { token: accepted }) if accepted == token
+    ))
+}
+
+// Keeps one connection per neighbour open for the ghost stream, reconnecting on
+// the next message after a failure
+fn send_ghosts(outgoing: Receiver<(String, ShardMessage)>, secret: &str) {
+    let mut streams: HashMap<String, TcpStream> = HashMap::new();
+    let mut down: HashSet<String> = HashSet::new();
+    while let Ok((addr, message)) = outgoing.recv() {
+        let Some(line) = encode_message(secret, &message) else {
+            continue;
+        };
+
+        if !streams.contains_key(&addr) {
+            match connect(&addr) {
+                Ok(stream) => {
+                    if down.remove(&addr) {
+                        println!("🔀 Ghost link to {} restored", addr);
+                    }
+                    streams.insert(addr.clone(), stream);
+                }
+                Err(e) => {
+                    if down.insert(addr.clone()) {
+                        println!("❌ Ghost link to {} failed: {}", addr, e);
+                    }
+                    continue;
+                }
+            }
+        }
+        if let Some(stream) = streams.get_mut(&addr)
+            && stream.write_all(line.as_bytes()).is_err()
+        {
+            streams.remove(&addr);
+        }
+    }
+}
+
+fn receive_shard_messages(
+    stream: TcpStream,
+    secret: &str,
+    incoming: Sender<PlayerHandoff>,
+    ghosts: Sender<(String, Vec<GhostState>)>,
+) {
+    let _ = stream.set_read_timeout(Some(HANDOFF_IO_TIMEOUT));
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if !matches!(reader.read_line(&mut line), Ok(read) if read > 0) {
+            return;
+        }
+
+        match decode_message(secret, &line) {
+            Some(ShardMessage::Handoff(handoff)) => {
+                let token = handoff.token.clone();
+                if incoming.send(handoff).is_err() {
+                    return;
+                }
+                if let Some(reply) = encode_message(secret, &ShardMessage::Accepted { token }) {
+                    let _ = writer.write_all(reply.as_bytes());
+                }
+            }
+            Some(ShardMessage::Ghosts { shard, entities }) => {
+                if ghosts.send((shard, entities)).is_err() {
+                    return;
+                }
+            }
+            _ => {
+                let peer = reader.get_ref().peer_addr().map_or_else(|_| "unknown".to_string(), |addr| addr.to_string());
+                println!("❌ Dropping shard connection from {}: unsigned or malformed message", peer);
+                return;
+            }
+        }
+    }
+}
+
+// ============================================================================
+// SHARD SYSTEMS
+// ============================================================================
+
+// Other shards spawn the NPCs placed in their regions
+pub fn claim_region_npcs_system(shard_map: Res<ShardMap>, mut spawn_list: ResMut<NpcSpawnList>) {
+    let local = shard_map.local();
+    spawn_list.spawns.retain(|spawn| local.contains(spawn.position[0]));
+}
+
+// Players in the main zone who walk out of this shard's region are offered to the
+// shard that owns where they are now. They stand still until it answers.
+pub fn shard_boundary_system(
+    mut commands: Commands,
+    shard_map: Res<ShardMap>,
+    link: Res<ShardLink>,
+    mut players: CrossingPlayerQuery,
+) {
+    for (entity, player, position, mut velocity, mut desired_velocity, profile, health, zone, account, inventory) in players.iter_mut() {
+        if *zone != MAIN_ZONE || shard_map.local().contains(position.x) {
+            continue;
+        }
+        let Some(shard) = shard_map.owner_of(position.x) else {
+            continue;
+        };
+
+        let token = format!("{:016x}", rand::random::<u64>());
+        let handoff = PlayerHandoff {
+            token: token.clone(),
+            account: account.map(|account| account.id.clone()),
+            position: *position,
+            velocity: *velocity,
+            profile: *profile,
+            health: health.current,
+            inventory: inventory.map(|inventory| inventory.slots.clone()).unwrap_or_default(),
+        };
+        let target = &shard_map.shards[shard];
+        println!("🔀 Handing player {} off to shard '{}'", player.id, target.name);
+        let _ = link.outgoing.send((target.handoff_addr.clone(), handoff));
+
+        *velocity = Velocity { x: 0.0, y: 0.0 };
+        *desired_velocity = DesiredVelocity::default();
+        commands.entity(entity).insert(PendingHandoff { token, shard });
+    }
+}
+
+// Accepted players are redirected and despawned here at once, since the other shard
+// now owns them; rejected ones are put back inside our region
+pub fn handoff_result_system(
+    mut commands: Commands,
+    shard_map: Res<ShardMap>,
+    link: Res<ShardLink>,
+    mut redirect_events: EventWriter<PlayerRedirectEvent>,
+    mut despawn_events: EventWriter<PlayerDespawnEvent>,
+    mut players: Query<(Entity, &Player, &PendingHandoff, &mut Position)>,
+) {
+    while let Ok(result) = link.results.try_recv() {
+        let Some((entity, player, pending, mut position)) = players.iter_mut()
+            .find(|(_, _, pending, _)| pending.token == result.token)
+        else {
+            continue;
+        };
+
+        if result.accepted {
+            redirect_events.send(PlayerRedirectEvent {
+                player_id: player.id,
+                url: shard_map.shards[pending.shard].client_url.clone(),
+                token: result.token,
+            });
+            despawn_events.send(PlayerDespawnEvent { player_id: player.id });
+            continue;
+        }
+
+        let local = shard_map.local();
+        position.x = position.x.clamp(local.min_x, local.max_x - 1.0);
+        commands.entity(entity).remove::<PendingHandoff>();
+        println!("❌ Shard '{}' did not take player {}, keeping them", shard_map.shards[pending.shard].name, player.id);
+    }
+}
+
+pub fn receive_handoffs_system(
+    tick: Res<SimulationTick>,
+    config: Res<ShardConfig>,
+    link: Res<ShardLink>,
+    mut arrivals: ResMut<HandoffArrivals>,
+) {
+    while let Ok(handoff) = link.incoming.try_recv() {
+        println!("🔀 Expecting handed-off player (token {})", handoff.token);
+        arrivals.pending.insert(handoff.token.clone(), (handoff, tick.0 + config.arrival_timeout_ticks));
+    }
+    arrivals.pending.retain(|_, (_, expires_tick)| tick.0 < *expires_tick);
+}
+
+// Runs once the reconnected player has spawned and received their starting inventory
+pub fn apply_handoff_system(
+    player_registry: Res<PlayerRegistry>,
+    mut arrivals: ResMut<HandoffArrivals>,
+    mut arrival_events: EventReader<PlayerHandoffArrivalEvent>,
+    mut players: ArrivingPlayerQuery,
+) {
+    for event in arrival_events.read() {
+        let Some(entity) = player_registry.get_player_entity(event.player_id) else {
+            continue;
+        };
+        let Ok((account, mut position, mut velocity, mut profile, mut health, inventory)) = players.get_mut(entity) else {
+            continue;
+        };
+        let Some((handoff, _)) = arrivals.pending.remove(&event.token) else {
+            println!("❌ Player {} presented an unknown handoff token", event.player_id);
+            continue;
+        };
+        if handoff.account.as_deref() != account.map(|account| account.id.as_str()) {
+            println!("❌ Player {} presented a handoff token for another account", event.player_id);
+            continue;
+        }
+
+        *position = handoff.position;
+        *velocity = handoff.velocity;
+        *profile = handoff.profile;
+        health.current = handoff.health.clamp(0.0, health.max);
+        if let Some(mut inventory) = inventory {
+            let capacity = inventory.slots.len();
+            inventory.slots = handoff.inventory;
+            inventory.slots.resize(capacity, None);
+            inventory.changed_slots = (0..capacity).collect();
+        }
+        println!("🔀 Player {} arrived by handoff at ({:.0}, {:.0})", event.player_id, position.x, position.y);
+    }
+}
+
+// Sends every other shard the entities we own within view range of its region. An
+// empty list is sent too, so the receiver knows to drop ghosts that walked away.
+pub fn send_ghosts_system(
+    shard_map: Res<ShardMap>,
+    config: Res<ShardConfig>,
+    link: Res<ShardLink>,
+    entities: Query<(&NetworkId, &NetworkEntityKind, &NetworkSnapshot, &Position, &ZoneId), Without<Ghost>>,
+) {
+    let local = shard_map.local();
+    for (index, shard) in shard_map.shards.iter().enumerate() {
+        if index == shard_map.this {
+            continue;
+        }
+        let ghosts = entities.iter()
+            .filter(|(_, _, snapshot, position, zone)| {
+                **zone == MAIN_ZONE && !snapshot.components.is_empty() && shard.distance_to(position.x) <= config.ghost_margin
+            })
+            .map(|(network_id, kind, snapshot, position, _)| GhostState {
+                network_id: network_id.0,
+                kind: *kind,
+                position: *position,
+                components: snapshot.components.clone(),
+            })
+            .collect();
+        let message = ShardMessage::Ghosts { shard: local.name.clone(), entities: ghosts };
+        let _ = link.ghosts_outgoing.try_send((shard.handoff_addr.clone(), message));
+    }
+}
+
+// Mirrors other shards' border entities as ghosts. Changed values are marked dirty
+// so clients get them as ordinary deltas.
+pub fn apply_ghosts_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    shard_map: Res<ShardMap>,
+    link: Res<ShardLink>,
+    mut registry: ResMut<GhostRegistry>,
+    mut allocator: ResMut<NetworkIdAllocator>,
+    mut ghosts: Query<(&mut Position, &mut NetworkSnapshot, &mut NetworkDirty), With<Ghost>>,
+) {
+    while let Ok((name, states)) = link.ghosts_incoming.try_recv() {
+        let Some(shard) = shard_map.shards.iter().position(|shard| shard.name == name) else {
+            continue;
+        };
+        registry.last_heard.insert(shard, tick.0);
+
+        let mut seen = HashSet::new();
+        for state in states {
+            seen.insert(state.network_id);
+            // A ghost spawned earlier this tick can't be updated until its commands apply
+            if let Some(&entity) = registry.ghosts.get(&(shard, state.network_id)) {
+                if let Ok((mut position, mut snapshot, mut dirty)) = ghosts.get_mut(entity) {
+                    if *position != state.position {
+                        *position = state.position;
+                    }
+                    for (key, value) in state.components {
+                        if snapshot.components.get(&key) == Some(&value) {
+                            continue;
+                        }
+                        if !dirty.changed_components.contains(&key) {
+                            dirty.changed_components.push(key.clone());
+                        }
+                        snapshot.components.insert(key, value);
+                    }
+                }
+                continue;
+            }
+
+            let mut networked = NetworkedEntityBundle::new(allocator.allocate(), state.kind);
+            networked.snapshot.components.extend(state.components);
+            let entity = commands.spawn((
+                networked,
+                state.position,
+                MAIN_ZONE,
+                Ghost,
+            )).id();
+            registry.ghosts.insert((shard, state.network_id), entity);
+        }
+
+        registry.ghosts.retain(|(ghost_shard, remote_id), entity| {
+            let keep = *ghost_shard != shard || seen.contains(remote_id);
+            if !keep {
+                commands.entity(*entity).despawn();
+            }
+            keep
+        });
+    }
+
+    let silent: Vec<usize> = registry.last_heard.iter()
+        .filter(|(_, heard)| tick.0 - **heard >= GHOST_TIMEOUT_TICKS)
+        .map(|(shard, _)| *shard)
+        .collect();
+    for shard in silent {
+        println!("❌ Shard '{}' went quiet, dropping its ghosts", shard_map.shards[shard].name);
+        registry.last_heard.remove(&shard);
+        registry.ghosts.retain(|(ghost_shard, _), entity| {
+            let keep = *ghost_shard != shard;
+            if !keep {
+                commands.entity(*entity).despawn();
+            }
+            keep
+        });
+    }
+}
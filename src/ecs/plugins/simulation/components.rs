@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+// ============================================================================
+// SIMULATION CONSTANTS
+// ============================================================================
+
+// Fixed simulation rate shared by the FixedUpdate schedule and deterministic mode
+pub const TICK_RATE_HZ: f64 = 10.0;
+
+// ============================================================================
+// SIMULATION RESOURCES
+// ============================================================================
+
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct SimulationConfig {
+    pub seed: u64,
+    pub deterministic: bool,
+}
+
+// Seeded RNG used by all spawning code. ChaCha8 is used instead of StdRng because
+// its output stream is stable across rand versions and platforms.
+#[derive(Resource, Deref, DerefMut)]
+pub struct SimulationRng(pub ChaCha8Rng);
+
+impl SimulationRng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self(ChaCha8Rng::seed_from_u64(seed))
+    }
+}
+
+// Number of fixed ticks simulated since startup
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulationTick(pub u64);
+
+// Stable ordering key for hashed entities: players first, then characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SimulationEntityKey {
+    Player(u32),
+    Character(u32),
+}
+
+// ============================================================================
+// HASHING
+// ============================================================================
+
+pub const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// Folds bytes into a running FNV-1a hash; start from FNV_OFFSET_BASIS
+pub fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| (hash ^ *byte as u64).wrapping_mul(FNV_PRIME))
+}
@@ -0,0 +1,53 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use components::{SimulationConfig, SimulationRng, SimulationTick, TICK_RATE_HZ};
+use systems::{advance_simulation_tick_system, is_deterministic, log_state_hash_system};
+
+// Simulation plugin: seeded RNG, tick counter and optional fixed-timestep-only mode.
+//
+// In deterministic mode virtual time advances by exactly one fixed timestep per
+// frame, so every frame runs exactly one FixedUpdate tick. Combined with the seeded
+// RNG, the same seed and the same input stream produce bit-identical state.
+#[derive(Default)]
+pub struct SimulationPlugin {
+    pub seed: Option<u64>,
+    pub deterministic: bool,
+}
+
+impl SimulationPlugin {
+    // Reads SIM_SEED and SIM_DETERMINISTIC from the environment
+    pub fn from_env() -> Self {
+        let seed = std::env::var("SIM_SEED").ok().and_then(|value| value.parse().ok());
+        let deterministic = std::env::var("SIM_DETERMINISTIC")
+            .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+
+        Self { seed, deterministic }
+    }
+
+    pub fn timestep() -> Duration {
+        Duration::from_secs_f64(1.0 / TICK_RATE_HZ)
+    }
+}
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        let seed = self.seed.unwrap_or_else(rand::random);
+        println!("🎲 Simulation seed: {} (deterministic: {})", seed, self.deterministic);
+
+        app.insert_resource(SimulationConfig { seed, deterministic: self.deterministic })
+            .insert_resource(SimulationRng::from_seed(seed))
+            .insert_resource(SimulationTick::default())
+            .insert_resource(Time::<Fixed>::from_duration(Self::timestep()))
+            .add_systems(FixedFirst, advance_simulation_tick_system)
+            .add_systems(FixedLast, log_state_hash_system.run_if(is_deterministic));
+
+        if self.deterministic {
+            app.insert_resource(TimeUpdateStrategy::ManualDuration(Self::timestep()));
+        }
+    }
+}
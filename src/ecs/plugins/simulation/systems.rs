@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use crate::ecs::components::{Character, Player, Position, Velocity};
+use super::components::*;
+
+pub type SimulationStateQuery<'w, 's> =
+    Query<'w, 's, (Option<&'static Player>, Option<&'static Character>, &'static Position, &'static Velocity)>;
+
+// ============================================================================
+// SIMULATION SYSTEMS
+// ============================================================================
+
+const STATE_HASH_LOG_INTERVAL: u64 = 100;
+
+pub fn advance_simulation_tick_system(mut tick: ResMut<SimulationTick>) {
+    tick.0 += 1;
+}
+
+// Periodically logs the state hash so two runs with the same seed can be compared
+pub fn log_state_hash_system(
+    tick: Res<SimulationTick>,
+    config: Res<SimulationConfig>,
+    query: SimulationStateQuery,
+) {
+    if tick.0.is_multiple_of(STATE_HASH_LOG_INTERVAL) {
+        println!("🔒 Tick {} (seed {}) state hash {:016x}", tick.0, config.seed, compute_state_hash(&query));
+    }
+}
+
+pub fn is_deterministic(config: Res<SimulationConfig>) -> bool {
+    config.deterministic
+}
+
+// Hashes the Position/Velocity state of every player and character
+pub fn compute_state_hash(
+    query: &SimulationStateQuery,
+) -> u64 {
+    let entries = query.iter()
+        .filter_map(|(player, character, position, velocity)| {
+            let key = match (player, character) {
+                (Some(player), _) => SimulationEntityKey::Player(player.id),
+                (None, Some(character)) => SimulationEntityKey::Character(character.id),
+                (None, None) => return None,
+            };
+            Some((key, [position.x, position.y, velocity.x, velocity.y]))
+        })
+        .collect();
+
+    hash_simulation_state(entries)
+}
+
+// ============================================================================
+// STATE HASHING
+// ============================================================================
+
+// FNV-1a over raw float bits, so two runs hash equal only if they are bit-identical.
+// Entries are sorted first, which keeps the hash independent of query iteration order.
+pub fn hash_simulation_state(mut entries: Vec<(SimulationEntityKey, [f32; 4])>) -> u64 {
+    entries.sort_by_key(|(key, _)| *key);
+
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut write = |bytes: &[u8]| hash = fnv1a(hash, bytes);
+
+    for (key, values) in entries {
+        match key {
+            SimulationEntityKey::Player(id) => {
+                write(&[0]);
+                write(&id.to_le_bytes());
+            }
+            SimulationEntityKey::Character(id) => {
+                write(&[1]);
+                write(&id.to_le_bytes());
+            }
+        }
+        for value in values {
+            write(&value.to_bits().to_le_bytes());
+        }
+    }
+
+    hash
+}
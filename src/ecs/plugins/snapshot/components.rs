@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::ecs::components::{CharacterProfile, Position, Velocity};
+use crate::ecs::plugins::health::components::SavedHealth;
+use crate::ecs::plugins::inventory::components::ItemStack;
+
+// ============================================================================
+// SNAPSHOT FILE FORMAT
+// ============================================================================
+
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 5;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    pub version: u32,
+    pub tick: u64,
+    // Last network ID handed out by NetworkIdAllocator
+    pub last_network_id: u32,
+    pub entities: Vec<EntitySnapshot>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum SnapshotEntityKind {
+    Player(u32),
+    Character(u32),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EntitySnapshot {
+    pub kind: SnapshotEntityKind,
+    pub network_id: Option<u32>,
+    pub position: Position,
+    pub velocity: Velocity,
+    pub profile: CharacterProfile,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archetype: Option<String>,
+    // Account of a saved player; connection-slot IDs are reused across restarts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    // Zone name (the template's, for a player in an instance); missing means the main zone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone: Option<String>,
+    // Inventory slots of a saved player
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inventory: Option<Vec<Option<ItemStack>>>,
+    // Health of a saved player
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<SavedHealth>,
+}
+
+impl WorldSnapshot {
+    pub fn load(path: &str) -> std::io::Result<Self> {
+        let data = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    // Write to a temporary file first so a crash mid-write never corrupts the last snapshot
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        if let Some(parent) = std::path::Path::new(path).parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = format!("{}.tmp", path);
+        std::fs::write(&tmp_path, serde_json::to_vec(self)?)?;
+        std::fs::rename(tmp_path, path)
+    }
+}
+
+// ============================================================================
+// SNAPSHOT RESOURCES
+// ============================================================================
+
+#[derive(Resource, Clone)]
+pub struct SnapshotConfig {
+    pub path: String,
+    pub interval_ticks: u64,
+}
+
+// State of players who aren't connected, from the last snapshot or from when they logged
+// off since; applied when a player with the same account spawns
+#[derive(Resource, Default)]
+pub struct RestoredPlayerStates {
+    pub players: HashMap<String, EntitySnapshot>,
+}
@@ -0,0 +1,52 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::{RestoredPlayerStates, SnapshotConfig};
+use systems::{
+    apply_restored_player_state_system, periodic_snapshot_system, restore_snapshot_system, save_departing_player_state_system,
+    shutdown_snapshot_system,
+};
+use crate::ecs::plugins::simulation::components::TICK_RATE_HZ;
+
+const DEFAULT_SNAPSHOT_INTERVAL_SECS: f64 = 60.0;
+
+// Snapshot plugin: saves networked entity state to disk periodically and on
+// shutdown, and restores the latest snapshot at startup
+pub struct SnapshotPlugin {
+    pub path: String,
+    pub interval_secs: f64,
+}
+
+impl SnapshotPlugin {
+    // Enabled when SNAPSHOT_PATH is set; SNAPSHOT_INTERVAL_SECS controls the save period
+    pub fn from_env() -> Option<Self> {
+        let path = std::env::var("SNAPSHOT_PATH").ok().filter(|path| !path.is_empty())?;
+        let interval_secs = std::env::var("SNAPSHOT_INTERVAL_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_SNAPSHOT_INTERVAL_SECS);
+
+        Some(Self { path, interval_secs })
+    }
+}
+
+impl Plugin for SnapshotPlugin {
+    fn build(&self, app: &mut App) {
+        let interval_ticks = (self.interval_secs * TICK_RATE_HZ).round() as u64;
+
+        app.insert_resource(SnapshotConfig { path: self.path.clone(), interval_ticks })
+            .insert_resource(RestoredPlayerStates::default())
+            .add_systems(Startup, restore_snapshot_system)
+            .add_systems(FixedUpdate, (
+                apply_restored_player_state_system
+                    .after(crate::ecs::systems::player_spawn_system)
+                    .before(crate::ecs::plugins::world_map::systems::relocate_blocked_spawns_system)
+                    .before(crate::ecs::plugins::zone::systems::restore_saved_zone_system)
+                    .before(crate::ecs::plugins::inventory::systems::attach_inventory_system),
+                save_departing_player_state_system.before(crate::ecs::systems::player_despawn_system),
+            ))
+            .add_systems(FixedLast, periodic_snapshot_system)
+            .add_systems(Last, shutdown_snapshot_system);
+    }
+}
@@ -0,0 +1,246 @@
+use bevy::ecs::query::ROQueryItem;
+use bevy::prelude::*;
+use crate::ecs::components::*;
+use crate::ecs::plugins::health::components::{Dead, Health, SavedHealth};
+use crate::ecs::plugins::inventory::components::{Inventory, SavedInventory};
+use crate::ecs::plugins::network::components::{NetworkEntityKind, NetworkId, NetworkIdAllocator, NetworkedEntityBundle};
+use crate::ecs::plugins::simulation::components::{SimulationRng, SimulationTick};
+use crate::ecs::plugins::zone::components::{SavedZone, ZoneId, Zones};
+use super::components::*;
+
+type SnapshotData = (
+    Option<&'static Player>,
+    Option<&'static Character>,
+    Option<&'static NetworkId>,
+    &'static Position,
+    &'static Velocity,
+    &'static CharacterProfile,
+    Option<&'static NpcArchetype>,
+    Option<&'static Account>,
+    &'static ZoneId,
+    Option<&'static Inventory>,
+    Option<&'static Health>,
+    Option<&'static Dead>,
+);
+
+type SnapshotQuery<'w, 's> = Query<'w, 's, SnapshotData>;
+
+type ReturningPlayerQuery<'w, 's> = Query<'w, 's, (
+    Entity,
+    &'static Player,
+    &'static Account,
+    &'static mut Position,
+    &'static mut Velocity,
+    &'static mut CharacterProfile,
+    &'static mut Health,
+), Added<Player>>;
+
+// ============================================================================
+// SNAPSHOT HELPERS
+// ============================================================================
+
+fn entity_snapshot(
+    zones: &Zones,
+    tick: u64,
+    (player, character, network_id, position, velocity, profile, archetype, account, zone, inventory, health, dead): ROQueryItem<SnapshotData>,
+) -> Option<EntitySnapshot> {
+    let kind = match (player, character) {
+        // Guests have nothing to match them up with after a restart
+        (Some(_), _) if account.is_none() => return None,
+        (Some(player), _) => SnapshotEntityKind::Player(player.id),
+        // Instances aren't saved, so neither is anything living in one
+        (None, Some(_)) if zones.instances.contains_key(zone) => return None,
+        (None, Some(character)) => SnapshotEntityKind::Character(character.id),
+        (None, None) => return None,
+    };
+    Some(EntitySnapshot {
+        kind,
+        network_id: network_id.map(|id| id.0),
+        position: *position,
+        velocity: *velocity,
+        profile: *profile,
+        archetype: archetype.map(|archetype| archetype.name.clone()),
+        account: account.map(|account| account.id.clone()),
+        zone: Some(zones.name(*zone).to_string()),
+        inventory: inventory.map(|inventory| inventory.slots.clone()),
+        health: player.and(health).map(|health| SavedHealth::new(health, dead, tick)),
+    })
+}
+
+fn build_snapshot(
+    tick: u64,
+    allocator: &NetworkIdAllocator,
+    restored_players: &RestoredPlayerStates,
+    zones: &Zones,
+    query: &SnapshotQuery,
+) -> WorldSnapshot {
+    let mut entities: Vec<EntitySnapshot> = query.iter()
+        .filter_map(|entity| entity_snapshot(zones, tick, entity))
+        .collect();
+
+    // Keep saved state for players who aren't connected
+    entities.extend(restored_players.players.values().cloned());
+
+    WorldSnapshot {
+        version: SNAPSHOT_FORMAT_VERSION,
+        tick,
+        last_network_id: allocator.last_allocated(),
+        entities,
+    }
+}
+
+fn write_snapshot(config: &SnapshotConfig, snapshot: &WorldSnapshot) {
+    match snapshot.save(&config.path) {
+        Ok(()) => println!("💾 Saved world snapshot ({} entities) to {}", snapshot.entities.len(), config.path),
+        Err(e) => println!("❌ Failed to save world snapshot to {}: {}", config.path, e),
+    }
+}
+
+// ============================================================================
+// SNAPSHOT SYSTEMS
+// ============================================================================
+
+pub fn restore_snapshot_system(
+    mut commands: Commands,
+    config: Res<SnapshotConfig>,
+    game_config: Res<GameConfig>,
+    zones: Res<Zones>,
+    mut rng: ResMut<SimulationRng>,
+    mut allocator: ResMut<NetworkIdAllocator>,
+    mut restored_players: ResMut<RestoredPlayerStates>,
+) {
+    let snapshot = match WorldSnapshot::load(&config.path) {
+        Ok(snapshot) => snapshot,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            println!("💾 No world snapshot at {}, starting fresh", config.path);
+            return;
+        }
+        Err(e) => {
+            println!("❌ Failed to load world snapshot {}: {}", config.path, e);
+            return;
+        }
+    };
+
+    // Never reuse network IDs from the previous run, including ones saved on entities
+    let highest_saved_id = snapshot.entities.iter().filter_map(|entity| entity.network_id).max().unwrap_or(0);
+    allocator.reserve_through(snapshot.last_network_id.max(highest_saved_id));
+
+    let mut characters = 0;
+    for entity in snapshot.entities {
+        match entity.kind {
+            SnapshotEntityKind::Character(character_id) => {
+                let Some(zone) = zones.find_saved(entity.zone.as_deref()) else {
+                    println!("❌ Skipped character {} saved in unknown zone {:?}", character_id, entity.zone);
+                    continue;
+                };
+                let mut bundle = CharacterBundle::new(character_id, Some(entity.position), &game_config, &mut **rng);
+                bundle.velocity = entity.velocity;
+                bundle.character_profile = entity.profile;
+                bundle.zone = zone;
+
+                let mut character = commands.spawn(bundle);
+                if let Some(network_id) = entity.network_id {
+                    character.insert(NetworkedEntityBundle::new(network_id, NetworkEntityKind::Npc));
+                }
+                if let Some(name) = entity.archetype {
+                    character.insert(NpcArchetype { name });
+                }
+                characters += 1;
+            }
+            SnapshotEntityKind::Player(_) => {
+                if let Some(account) = entity.account.clone() {
+                    restored_players.players.insert(account, entity);
+                }
+            }
+        }
+    }
+
+    println!(
+        "💾 Restored world snapshot from tick {}: {} characters, {} player states",
+        snapshot.tick, characters, restored_players.players.len()
+    );
+}
+
+// Place returning players where the snapshot left them, as they were and with what they were carrying
+pub fn apply_restored_player_state_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    zones: Res<Zones>,
+    mut restored_players: ResMut<RestoredPlayerStates>,
+    mut query: ReturningPlayerQuery,
+) {
+    if restored_players.players.is_empty() {
+        return;
+    }
+
+    for (entity, player, account, mut position, mut velocity, mut profile, mut health) in query.iter_mut() {
+        if let Some(state) = restored_players.players.remove(&account.id) {
+            *position = state.position;
+            *velocity = state.velocity;
+            *profile = state.profile;
+            match zones.find_saved(state.zone.as_deref()) {
+                Some(zone) => {
+                    commands.entity(entity).insert(SavedZone { zone, position: Vec2::new(position.x, position.y) });
+                }
+                None => println!("❌ Player {} was saved in unknown zone {:?}", player.id, state.zone),
+            }
+            if let Some(slots) = state.inventory {
+                commands.entity(entity).insert(SavedInventory(slots));
+            }
+            if let Some(dead) = state.health.and_then(|saved| saved.restore(&mut health, tick.0)) {
+                commands.entity(entity).insert(dead);
+            }
+            println!("💾 Restored player {} (account {}) at ({:.1}, {:.1})", player.id, account.id, position.x, position.y);
+        }
+    }
+}
+
+// Runs before the despawn system so the entity still exists. Players who log off between
+// snapshots keep their state until they return, and it goes into the next snapshot.
+pub fn save_departing_player_state_system(
+    tick: Res<SimulationTick>,
+    zones: Res<Zones>,
+    player_registry: Res<PlayerRegistry>,
+    mut despawn_events: EventReader<PlayerDespawnEvent>,
+    mut restored_players: ResMut<RestoredPlayerStates>,
+    query: SnapshotQuery,
+) {
+    for event in despawn_events.read() {
+        let Some(entity) = player_registry.get_player_entity(event.player_id) else {
+            continue;
+        };
+        if let Ok(player) = query.get(entity)
+            && let Some(state) = entity_snapshot(&zones, tick.0, player)
+            && let Some(account) = state.account.clone()
+        {
+            restored_players.players.insert(account, state);
+        }
+    }
+}
+
+pub fn periodic_snapshot_system(
+    tick: Res<SimulationTick>,
+    config: Res<SnapshotConfig>,
+    allocator: Res<NetworkIdAllocator>,
+    restored_players: Res<RestoredPlayerStates>,
+    zones: Res<Zones>,
+    query: SnapshotQuery,
+) {
+    if config.interval_ticks > 0 && tick.0 > 0 && tick.0.is_multiple_of(config.interval_ticks) {
+        write_snapshot(&config, &build_snapshot(tick.0, &allocator, &restored_players, &zones, &query));
+    }
+}
+
+pub fn shutdown_snapshot_system(
+    mut exit_events: EventReader<AppExit>,
+    tick: Res<SimulationTick>,
+    config: Res<SnapshotConfig>,
+    allocator: Res<NetworkIdAllocator>,
+    restored_players: Res<RestoredPlayerStates>,
+    zones: Res<Zones>,
+    query: SnapshotQuery,
+) {
+    if exit_events.read().next().is_some() {
+        write_snapshot(&config, &build_snapshot(tick.0, &allocator, &restored_players, &zones, &query));
+    }
+}
@@ -10,9 +10,9 @@ use crate::ecs::plugins::network::components::NetworkMessage;
 // Messages from WebSocket to ECS
 #[derive(Debug, Clone)]
 pub enum WebSocketMessage {
-    PlayerJoined(u32),
-    PlayerLeft(u32),
-    PlayerInput(u32, InputCommand),
+    Joined(u32, Option<String>, Option<String>), // player_id, account, handoff token
+    Left(u32),
+    Input(u32, InputCommand),
 }
 
 // WebSocket connection resource
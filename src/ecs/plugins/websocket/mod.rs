@@ -3,28 +3,48 @@ pub mod systems;
 
 use bevy::prelude::*;
 use components::WebSocketConnections;
-use systems::{handle_websocket_messages, send_network_updates, setup_websocket_server};
+use systems::{handle_websocket_messages, kick_players_system, redirect_players_system, send_network_updates, setup_websocket_server};
 
 // WebSocket plugin
 pub struct WebSocketPlugin {
     pub port: u16,
+    // Key that account signatures are checked against; accounts are refused without one
+    pub account_secret: Option<String>,
 }
 
 impl Default for WebSocketPlugin {
     fn default() -> Self {
-        Self { port: 5000 }
+        Self { port: 5000, account_secret: None }
+    }
+}
+
+impl WebSocketPlugin {
+    // WEBSOCKET_PORT overrides the default port, e.g. to run several shards on one host;
+    // ACCOUNT_SECRET enables signed ?account= logins
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let port = std::env::var("WEBSOCKET_PORT")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(defaults.port);
+        let account_secret = std::env::var("ACCOUNT_SECRET").ok().filter(|secret| !secret.is_empty());
+
+        Self { port, account_secret }
     }
 }
 
 impl Plugin for WebSocketPlugin {
     fn build(&self, app: &mut App) {
         let port = self.port;
+        let account_secret = self.account_secret.clone();
         app.insert_resource(WebSocketConnections::default())
             .add_systems(Startup, move |connections: Res<WebSocketConnections>| {
-                setup_websocket_server(connections, port);
+                setup_websocket_server(connections, port, account_secret.clone());
             })
             .add_systems(Update, (
                 handle_websocket_messages.before(crate::ecs::systems::player_spawn_system),
+                kick_players_system,
+                redirect_players_system,
                 send_network_updates
                     .after(crate::ecs::plugins::network::systems::build_delta_updates_system)
                     .after(crate::ecs::plugins::network::systems::build_full_sync_system),
@@ -1,5 +1,9 @@
 use bevy::prelude::*;
-use tokio_tungstenite::{accept_async, tungstenite::Message};
+use tokio_tungstenite::{accept_hdr_async, tungstenite::Message};
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
 use tokio::net::{TcpListener, TcpStream};
 use futures_util::{SinkExt, StreamExt};
 use std::collections::HashMap;
@@ -8,14 +12,18 @@ use tokio::sync::Mutex;
 use crossbeam_channel::Sender;
 use std::thread;
 use crate::ecs::components::*;
-use crate::ecs::plugins::network::components::NetworkUpdates;
+use crate::ecs::plugins::network::components::{
+    verify_message, EntityUpdate, NetworkMessage, NetworkUpdates, REDIRECT_TOKEN_KEY, REDIRECT_TYPE, REDIRECT_URL_KEY,
+};
 use super::components::*;
 
 // Setup WebSocket server in dedicated async runtime
 pub fn setup_websocket_server(
     connections: Res<WebSocketConnections>,
     port: u16,
+    account_secret: Option<String>,
 ) {
+    let account_secret: Option<Arc<str>> = account_secret.map(Into::into);
     let connections_clone = connections.connections.clone();
     let message_sender = connections.outgoing_sender.clone();
     let network_receiver = connections.network_receiver.clone();
@@ -85,19 +93,41 @@ pub fn setup_websocket_server(
                 println!("📡 New connection from: {}", addr);
                 let connections = connections_clone.clone();
                 let sender = message_sender.clone();
-                tokio::spawn(handle_client(stream, connections, sender));
+                tokio::spawn(handle_client(stream, connections, sender, account_secret.clone()));
             }
         });
     });
 }
 
 // Handle individual WebSocket client
+// (the handshake callback's error type is fixed by tungstenite)
+#[allow(clippy::result_large_err)]
 async fn handle_client(
     stream: TcpStream,
     connections: Arc<Mutex<HashMap<u32, tokio::sync::mpsc::UnboundedSender<Message>>>>,
     message_sender: Sender<WebSocketMessage>,
+    account_secret: Option<Arc<str>>,
 ) {
-    let ws_stream = match accept_async(stream).await {
+    // Clients identify their account with ?account=<id>&sig=<signature> on the
+    // connection URL, and players redirected by another shard bring its token as ?handoff=<token>
+    let mut account = None;
+    let mut handoff = None;
+    let ws_stream = match accept_hdr_async(stream, |request: &Request, response: Response| {
+        let query = request.uri().query();
+        handoff = parse_query_token(query, "handoff=", MAX_HANDOFF_TOKEN_LENGTH);
+        match authenticate_account(query, account_secret.as_deref()) {
+            Ok(authenticated) => {
+                account = authenticated;
+                Ok(response)
+            }
+            Err(reason) => {
+                println!("❌ Rejected connection: {}", reason);
+                let mut rejection = ErrorResponse::new(Some(reason.to_string()));
+                *rejection.status_mut() = StatusCode::UNAUTHORIZED;
+                Err(rejection)
+            }
+        }
+    }).await {
         Ok(ws) => ws,
         Err(e) => {
             println!("❌ WebSocket handshake failed: {}", e);
@@ -124,23 +154,40 @@ async fn handle_client(
         player_id
     };
     
-    println!("✅ Player {} connected", player_id);
+    match &account {
+        Some(account) => println!("✅ Player {} connected (account {})", player_id, account),
+        None => println!("✅ Player {} connected", player_id),
+    }
     
     // Notify ECS that player joined
-    let _ = message_sender.send(WebSocketMessage::PlayerJoined(player_id));
+    let _ = message_sender.send(WebSocketMessage::Joined(player_id, account, handoff));
     
-    // Spawn task to handle outgoing messages
+    // Spawn task to handle outgoing messages. A close sent by the server (kick or
+    // handoff) ends the connection without waiting for the client to answer it.
     let tx_clone = tx.clone();
+    let (closed_tx, mut closed_rx) = tokio::sync::oneshot::channel::<()>();
     tokio::spawn(async move {
         while let Some(msg) = rx.recv().await {
-            if ws_sender.send(msg).await.is_err() {
+            let closing = matches!(msg, Message::Close(_));
+            if ws_sender.send(msg).await.is_err() || closing {
                 break;
             }
         }
+        let _ = closed_tx.send(());
     });
     
-    // Handle incoming messages
-    while let Some(msg) = ws_receiver.next().await {
+    // Handle incoming messages until the client leaves or the server closes the connection
+    loop {
+        let msg = tokio::select! {
+            msg = ws_receiver.next() => msg,
+            _ = &mut closed_rx => {
+                println!("🔌 Closed connection of player {}", player_id);
+                break;
+            }
+        };
+        let Some(msg) = msg else {
+            break;
+        };
         match msg {
             Ok(Message::Text(text)) => {
                 if let Ok(input) = serde_json::from_str::<serde_json::Value>(&text) {
@@ -160,16 +207,51 @@ async fn handle_client(
     
     // Clean up connection
     connections.lock().await.remove(&player_id);
-    let _ = message_sender.send(WebSocketMessage::PlayerLeft(player_id));
+    let _ = message_sender.send(WebSocketMessage::Left(player_id));
     println!("🧹 Cleaned up connection for player {}", player_id);
 }
 
+const MAX_ACCOUNT_ID_LENGTH: usize = 32;
+// Shards issue 64-bit tokens as 16 hex digits
+const MAX_HANDOFF_TOKEN_LENGTH: usize = 16;
+
+// An account is only accepted with sig=<hex HMAC-SHA256 of the account ID under
+// ACCOUNT_SECRET>, issued by whatever service logs players in
+fn authenticate_account(query: Option<&str>, secret: Option<&str>) -> Result<Option<String>, &'static str> {
+    if query_value(query, "account=").is_none() {
+        return Ok(None);
+    }
+    let account = parse_query_token(query, "account=", MAX_ACCOUNT_ID_LENGTH).ok_or("invalid account id")?;
+    let secret = secret.ok_or("accounts are disabled (ACCOUNT_SECRET is not set)")?;
+    let signature = query_value(query, "sig=").ok_or("missing account signature")?;
+    if !verify_message(secret, account.as_bytes(), signature) {
+        return Err("invalid account signature");
+    }
+    Ok(Some(account))
+}
+
+fn query_value<'a>(query: Option<&'a str>, prefix: &str) -> Option<&'a str> {
+    query?.split('&').find_map(|pair| pair.strip_prefix(prefix))
+}
+
+// Extract a value from the connection query string, rejecting anything that isn't a
+// short identifier of letters, digits, '_' and '-' (account IDs are used as storage keys)
+fn parse_query_token(query: Option<&str>, prefix: &str, max_length: usize) -> Option<String> {
+    let value = query_value(query, prefix)?;
+    
+    let valid = !value.is_empty()
+        && value.len() <= max_length
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    
+    valid.then(|| value.to_string())
+}
+
 // Handle input messages from WebSocket
 async fn handle_input_message(player_id: u32, input: serde_json::Value, message_sender: &Sender<WebSocketMessage>) {
     // Parse input and send to ECS
     match serde_json::from_value::<InputCommand>(input.clone()) {
         Ok(command) => {
-            let _ = message_sender.send(WebSocketMessage::PlayerInput(player_id, command));
+            let _ = message_sender.send(WebSocketMessage::Input(player_id, command));
         }
         Err(_) => {
             println!("📥 Player {} sent invalid input: {:?}", player_id, input);
@@ -179,32 +261,82 @@ async fn handle_input_message(player_id: u32, input: serde_json::Value, message_
 
 // System to handle WebSocket messages and convert to ECS events
 pub fn handle_websocket_messages(
-    mut input_events: EventWriter<InputCommandEvent>,
+    mut input_events: EventWriter<ClientInputEvent>,
     mut spawn_events: EventWriter<PlayerSpawnEvent>,
     mut despawn_events: EventWriter<PlayerDespawnEvent>,
+    mut arrival_events: EventWriter<PlayerHandoffArrivalEvent>,
     connections: Res<WebSocketConnections>,
 ) {
     // Process all incoming messages from WebSocket
     while let Ok(message) = connections.incoming_messages.try_recv() {
         match message {
-            WebSocketMessage::PlayerJoined(player_id) => {
+            WebSocketMessage::Joined(player_id, account, handoff) => {
                 println!("🌐 WebSocket: Player {} connected", player_id);
                 // Just send the spawn event - let other systems handle spawning
-                spawn_events.send(PlayerSpawnEvent { player_id });
+                spawn_events.send(PlayerSpawnEvent { player_id, account });
+                if let Some(token) = handoff {
+                    arrival_events.send(PlayerHandoffArrivalEvent { player_id, token });
+                }
             }
-            WebSocketMessage::PlayerLeft(player_id) => {
+            WebSocketMessage::Left(player_id) => {
                 println!("🌐 WebSocket: Player {} disconnected", player_id);
                 // Just send the despawn event - let other systems handle despawning
                 despawn_events.send(PlayerDespawnEvent { player_id });
             }
-            WebSocketMessage::PlayerInput(player_id, command) => {
-                // Send input event
-                input_events.send(InputCommandEvent { player_id, command });
+            WebSocketMessage::Input(player_id, command) => {
+                // Send input event (validated by anti-cheat before the simulation sees it)
+                input_events.send(ClientInputEvent { player_id, command });
             }
         }
     }
 }
 
+// Close the connections of kicked players; the connection task ends once the close is
+// sent, whether or not the client answers it, and the disconnect path despawns them
+pub fn kick_players_system(
+    mut kick_events: EventReader<PlayerKickEvent>,
+    connections: Res<WebSocketConnections>,
+) {
+    for event in kick_events.read() {
+        let conns = connections.connections.blocking_lock();
+        if let Some(sender) = conns.get(&event.player_id) {
+            println!("👢 Kicking player {}: {}", event.player_id, event.reason);
+            let _ = sender.send(Message::Close(Some(CloseFrame {
+                code: CloseCode::Policy,
+                reason: event.reason.clone().into(),
+            })));
+        }
+    }
+}
+
+// Sends the redirect and then closes the connection, both on the connection's own
+// queue so the client always gets the redirect first
+pub fn redirect_players_system(
+    mut redirect_events: EventReader<PlayerRedirectEvent>,
+    connections: Res<WebSocketConnections>,
+) {
+    for event in redirect_events.read() {
+        let conns = connections.connections.blocking_lock();
+        let Some(sender) = conns.get(&event.player_id) else {
+            continue;
+        };
+        let mut components = HashMap::new();
+        components.insert(REDIRECT_URL_KEY.to_string(), serde_json::Value::String(event.url.clone()));
+        components.insert(REDIRECT_TOKEN_KEY.to_string(), serde_json::Value::String(event.token.clone()));
+        let message = NetworkMessage {
+            message_type: REDIRECT_TYPE.to_string(),
+            entity_updates: vec![EntityUpdate { network_id: event.player_id, components }],
+            tick: None,
+        };
+        println!("🔀 Redirecting player {} to {}", event.player_id, event.url);
+        let _ = sender.send(Message::Text(serde_json::to_string(&message).unwrap_or_default().into()));
+        let _ = sender.send(Message::Close(Some(CloseFrame {
+            code: CloseCode::Normal,
+            reason: "handoff".into(),
+        })));
+    }
+}
+
 // System to send network updates via WebSocket
 pub fn send_network_updates(
     mut network_updates: ResMut<NetworkUpdates>,
@@ -225,4 +357,57 @@ pub fn send_network_updates(
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::plugins::network::components::sign_message;
+
+    const SECRET: &str = "test-secret";
+
+    fn login(account: &str) -> String {
+        format!("account={}&sig={}", account, sign_message(SECRET, account.as_bytes()))
+    }
+
+    #[test]
+    fn accepts_a_signed_account() {
+        assert_eq!(authenticate_account(Some(&login("alice_01")), Some(SECRET)), Ok(Some("alice_01".to_string())));
+        assert_eq!(authenticate_account(Some(&login(&"a".repeat(32))), Some(SECRET)), Ok(Some("a".repeat(32))));
+    }
+
+    #[test]
+    fn connections_without_an_account_are_guests() {
+        assert_eq!(authenticate_account(None, Some(SECRET)), Ok(None));
+        assert_eq!(authenticate_account(Some("handoff=0123456789abcdef"), None), Ok(None));
+    }
+
+    #[test]
+    fn rejects_account_ids_that_are_not_plain_identifiers() {
+        for account in ["../x", "a/b", "", "%2E%2E%2Fx", "a.json"] {
+            assert_eq!(authenticate_account(Some(&login(account)), Some(SECRET)), Err("invalid account id"), "{:?}", account);
+        }
+        assert_eq!(authenticate_account(Some(&login(&"a".repeat(33))), Some(SECRET)), Err("invalid account id"));
+    }
+
+    #[test]
+    fn rejects_missing_or_wrong_signatures() {
+        assert_eq!(authenticate_account(Some("account=alice"), Some(SECRET)), Err("missing account signature"));
+        let forged = format!("account=alice&sig={}", sign_message("other-secret", b"alice"));
+        assert_eq!(authenticate_account(Some(&forged), Some(SECRET)), Err("invalid account signature"));
+        let borrowed = format!("account=alice&sig={}", sign_message(SECRET, b"bob"));
+        assert_eq!(authenticate_account(Some(&borrowed), Some(SECRET)), Err("invalid account signature"));
+    }
+
+    #[test]
+    fn rejects_accounts_when_no_secret_is_configured() {
+        assert_eq!(authenticate_account(Some(&login("alice")), None), Err("accounts are disabled (ACCOUNT_SECRET is not set)"));
+    }
+
+    #[test]
+    fn handoff_tokens_have_their_own_length_limit() {
+        let token = "0123456789abcdef";
+        assert_eq!(parse_query_token(Some(&format!("handoff={}", token)), "handoff=", MAX_HANDOFF_TOKEN_LENGTH), Some(token.to_string()));
+        assert_eq!(parse_query_token(Some(&format!("handoff={}0", token)), "handoff=", MAX_HANDOFF_TOKEN_LENGTH), None);
+    }
+}
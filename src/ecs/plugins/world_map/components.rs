@@ -0,0 +1,213 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use crate::ecs::plugins::simulation::components::{fnv1a, FNV_OFFSET_BASIS};
+
+// ============================================================================
+// MAP FILE FORMAT
+// ============================================================================
+
+// Simple grid map: each row is a string where '#' marks a blocked tile and any
+// other character is walkable. Row 0 covers y in [0, tile_size).
+#[derive(Debug, Deserialize)]
+pub struct MapFile {
+    pub id: String,
+    pub tile_size: f32,
+    pub width: u32,
+    pub height: u32,
+    pub rows: Vec<String>,
+}
+
+pub const BLOCKED_TILE: char = '#';
+
+// Keeps tile spans from including a tile the body only touches at its edge
+pub const MAP_EPSILON: f32 = 0.001;
+
+// ============================================================================
+// MAP RESOURCES
+// ============================================================================
+
+#[derive(Resource, Debug, Clone)]
+pub struct WorldMap {
+    pub id: String,
+    // FNV-1a hash of the map file, sent to clients so they can verify their copy
+    pub hash: u64,
+    pub tile_size: f32,
+    pub width: u32,
+    pub height: u32,
+    pub blocked: Vec<bool>,
+}
+
+impl WorldMap {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+        let file: MapFile = serde_json::from_slice(&bytes).map_err(|e| e.to_string())?;
+
+        if file.rows.len() != file.height as usize {
+            return Err(format!("expected {} rows, found {}", file.height, file.rows.len()));
+        }
+        if file.tile_size <= 0.0 {
+            return Err("tile_size must be positive".to_string());
+        }
+
+        let mut blocked = Vec::with_capacity((file.width * file.height) as usize);
+        for (y, row) in file.rows.iter().enumerate() {
+            if row.chars().count() != file.width as usize {
+                return Err(format!("row {} has {} tiles, expected {}", y, row.chars().count(), file.width));
+            }
+            blocked.extend(row.chars().map(|tile| tile == BLOCKED_TILE));
+        }
+
+        let hash = fnv1a(FNV_OFFSET_BASIS, &bytes);
+
+        Ok(Self {
+            id: file.id,
+            hash,
+            tile_size: file.tile_size,
+            width: file.width,
+            height: file.height,
+            blocked,
+        })
+    }
+
+    pub fn bounds(&self) -> Vec2 {
+        Vec2::new(self.width as f32 * self.tile_size, self.height as f32 * self.tile_size)
+    }
+
+    pub fn tile_of(&self, value: f32) -> i32 {
+        (value / self.tile_size).floor() as i32
+    }
+
+    // Tiles outside the map count as open; world edges are handled by boundary_system
+    pub fn is_blocked(&self, tile_x: i32, tile_y: i32) -> bool {
+        if tile_x < 0 || tile_y < 0 || tile_x >= self.width as i32 || tile_y >= self.height as i32 {
+            return false;
+        }
+        self.blocked[(tile_y as u32 * self.width + tile_x as u32) as usize]
+    }
+
+    pub fn tile_center(&self, tile_x: i32, tile_y: i32) -> Vec2 {
+        Vec2::new(
+            (tile_x as f32 + 0.5) * self.tile_size,
+            (tile_y as f32 + 0.5) * self.tile_size,
+        )
+    }
+
+    // Returns true if a square of half-extent `radius` overlaps any blocked tile
+    pub fn overlaps_blocked(&self, position: Vec2, radius: f32) -> bool {
+        let (min_x, max_x) = self.tile_span(position.x, radius);
+        let (min_y, max_y) = self.tile_span(position.y, radius);
+        (min_y..=max_y).any(|y| (min_x..=max_x).any(|x| self.is_blocked(x, y)))
+    }
+
+    // Inclusive range of tiles covered by [center - radius, center + radius)
+    pub fn tile_span(&self, center: f32, radius: f32) -> (i32, i32) {
+        let min = self.tile_of(center - radius);
+        let max = self.tile_of(center + radius - MAP_EPSILON);
+        (min, max.max(min))
+    }
+
+    // Moves a square of half-extent `radius` out of any blocked tiles it overlaps, each
+    // time along the axis of least penetration. Returns None if it overlapped nothing.
+    pub fn push_out(&self, position: Vec2, radius: f32) -> Option<Vec2> {
+        let mut resolved = position;
+        // A body can straddle at most a few tiles, so a handful of passes settles it
+        for _ in 0..4 {
+            let (min_x, max_x) = self.tile_span(resolved.x, radius);
+            let (min_y, max_y) = self.tile_span(resolved.y, radius);
+            let Some((tile_x, tile_y)) = (min_y..=max_y)
+                .flat_map(|y| (min_x..=max_x).map(move |x| (x, y)))
+                .find(|(x, y)| self.is_blocked(*x, *y))
+            else {
+                break;
+            };
+
+            let tile_min = Vec2::new(tile_x as f32, tile_y as f32) * self.tile_size;
+            let tile_max = tile_min + Vec2::splat(self.tile_size);
+            let center = self.tile_center(tile_x, tile_y);
+            let push_x = if resolved.x < center.x { tile_min.x - radius - resolved.x } else { tile_max.x + radius - resolved.x };
+            let push_y = if resolved.y < center.y { tile_min.y - radius - resolved.y } else { tile_max.y + radius - resolved.y };
+            if push_x.abs() <= push_y.abs() {
+                resolved.x += push_x;
+            } else {
+                resolved.y += push_y;
+            }
+        }
+        (resolved != position).then_some(resolved)
+    }
+
+    // Walks the tiles a segment crosses in order and returns the fraction of the way
+    // along it where the first blocked tile is entered, or None if the line is clear
+    pub fn segment_blocked_at(&self, from: Vec2, to: Vec2) -> Option<f32> {
+        let mut tile = (self.tile_of(from.x), self.tile_of(from.y));
+        if self.is_blocked(tile.0, tile.1) {
+            return Some(0.0);
+        }
+
+        let delta = to - from;
+        let step = (delta.x.signum() as i32, delta.y.signum() as i32);
+        // Fraction of the segment at which it crosses the next tile edge on each axis
+        let first_crossing = |position: f32, delta: f32, tile: i32| {
+            if delta > 0.0 {
+                ((tile + 1) as f32 * self.tile_size - position) / delta
+            } else if delta < 0.0 {
+                (tile as f32 * self.tile_size - position) / delta
+            } else {
+                f32::INFINITY
+            }
+        };
+        let mut next = Vec2::new(first_crossing(from.x, delta.x, tile.0), first_crossing(from.y, delta.y, tile.1));
+        let per_tile = Vec2::new(self.tile_size / delta.x.abs(), self.tile_size / delta.y.abs());
+
+        let last = (self.tile_of(to.x), self.tile_of(to.y));
+        let crossings = (last.0 - tile.0).abs() + (last.1 - tile.1).abs();
+        for _ in 0..crossings {
+            let t = if next.x < next.y {
+                tile.0 += step.0;
+                next.x += per_tile.x;
+                next.x - per_tile.x
+            } else {
+                tile.1 += step.1;
+                next.y += per_tile.y;
+                next.y - per_tile.y
+            };
+            if t > 1.0 {
+                break;
+            }
+            if self.is_blocked(tile.0, tile.1) {
+                return Some(t);
+            }
+        }
+        None
+    }
+
+    // Sweeps a square of half-extent `radius` along one axis. Returns the furthest
+    // coordinate reachable on that axis before touching a blocked tile, or None if
+    // the whole move is free. `cross` is the position on the other axis.
+    pub fn sweep_axis(&self, start: f32, delta: f32, cross: f32, radius: f32, horizontal: bool) -> Option<f32> {
+        if delta == 0.0 {
+            return None;
+        }
+
+        let (cross_min, cross_max) = self.tile_span(cross, radius);
+        let blocked_line = |tile: i32| {
+            (cross_min..=cross_max).any(|other| {
+                if horizontal { self.is_blocked(tile, other) } else { self.is_blocked(other, tile) }
+            })
+        };
+
+        if delta > 0.0 {
+            let first = self.tile_of(start + radius - MAP_EPSILON);
+            let last = self.tile_of(start + delta + radius - MAP_EPSILON);
+            (first..=last)
+                .find(|tile| blocked_line(*tile))
+                .map(|tile| tile as f32 * self.tile_size - radius)
+        } else {
+            let first = self.tile_of(start - radius);
+            let last = self.tile_of(start + delta - radius);
+            (last..=first)
+                .rev()
+                .find(|tile| blocked_line(*tile))
+                .map(|tile| (tile + 1) as f32 * self.tile_size + radius)
+        }
+    }
+}
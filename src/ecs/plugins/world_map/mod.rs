@@ -0,0 +1,49 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::WorldMap;
+use systems::{apply_map_bounds_system, map_collision_system, map_push_out_system, relocate_blocked_spawns_system};
+
+const DEFAULT_MAP_PATH: &str = "data/maps/default.json";
+
+// World map plugin: loads walls and blocked tiles from a data file and keeps
+// moving bodies out of them
+pub struct WorldMapPlugin {
+    pub path: String,
+}
+
+impl WorldMapPlugin {
+    // WORLD_MAP overrides the default map file
+    pub fn from_env() -> Self {
+        let path = std::env::var("WORLD_MAP").unwrap_or_else(|_| DEFAULT_MAP_PATH.to_string());
+        Self { path }
+    }
+}
+
+impl Plugin for WorldMapPlugin {
+    fn build(&self, app: &mut App) {
+        let map = match WorldMap::load(&self.path) {
+            Ok(map) => map,
+            Err(e) => {
+                println!("❌ Failed to load world map {}: {} (running without obstacles)", self.path, e);
+                return;
+            }
+        };
+
+        app.insert_resource(map)
+            .add_systems(Startup, apply_map_bounds_system)
+            .add_systems(FixedUpdate, (
+                map_collision_system
+                    .after(crate::ecs::systems::acceleration_friction_system)
+                    .before(crate::ecs::systems::movement_system),
+                map_push_out_system
+                    .after(crate::ecs::plugins::collision::systems::collision_resolution_system)
+                    .before(crate::ecs::systems::boundary_system),
+                relocate_blocked_spawns_system
+                    .after(crate::ecs::systems::player_spawn_system)
+                    .after(crate::ecs::systems::character_spawn_system)
+                    .before(map_collision_system),
+            ));
+    }
+}
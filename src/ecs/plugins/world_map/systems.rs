@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use std::collections::{HashSet, VecDeque};
+use crate::ecs::components::{GameConfig, Position, Velocity};
+use crate::ecs::plugins::collision::components::Collider;
+use crate::ecs::plugins::zone::components::{ZoneId, MAIN_ZONE};
+use super::components::*;
+
+const MIN_VELOCITY_THRESHOLD: f32 = 0.01;
+
+// ============================================================================
+// WORLD MAP SYSTEMS
+// ============================================================================
+
+pub fn apply_map_bounds_system(map: Res<WorldMap>, mut game_config: ResMut<GameConfig>) {
+    game_config.world_bounds = map.bounds();
+    println!(
+        "🗺️ Loaded map '{}' ({}x{} tiles, hash {:016x})",
+        map.id, map.width, map.height, map.hash
+    );
+}
+
+// Sweeps this tick's motion against blocked tiles before movement_system runs.
+// Each axis is resolved separately: on contact the body is placed against the tile
+// and that velocity axis is zeroed, so it slides along walls. The map only covers the main zone.
+pub fn map_collision_system(
+    time: Res<Time<Fixed>>,
+    map: Res<WorldMap>,
+    mut query: Query<(&mut Position, &mut Velocity, &Collider, &ZoneId)>,
+) {
+    let dt = time.timestep().as_secs_f32();
+
+    for (mut position, mut velocity, collider, zone) in query.iter_mut() {
+        if *zone != MAIN_ZONE {
+            continue;
+        }
+        if velocity.x.abs() < MIN_VELOCITY_THRESHOLD && velocity.y.abs() < MIN_VELOCITY_THRESHOLD {
+            continue;
+        }
+
+        let delta_x = velocity.x * dt;
+        if let Some(contact) = map.sweep_axis(position.x, delta_x, position.y, collider.radius, true) {
+            position.x = contact;
+            velocity.x = 0.0;
+        }
+
+        // The vertical sweep uses the horizontal end point so corners are respected
+        let end_x = position.x + velocity.x * dt;
+        let delta_y = velocity.y * dt;
+        if let Some(contact) = map.sweep_axis(position.y, delta_y, end_x, collider.radius, false) {
+            position.y = contact;
+            velocity.y = 0.0;
+        }
+    }
+}
+
+// Body-body pushes happen after the sweep above, so bodies shoved into a wall are
+// moved back out and stop pushing into it
+pub fn map_push_out_system(
+    map: Res<WorldMap>,
+    mut query: Query<(&mut Position, Option<&mut Velocity>, &Collider, &ZoneId)>,
+) {
+    for (mut position, velocity, collider, zone) in query.iter_mut() {
+        if *zone != MAIN_ZONE || collider.sensor {
+            continue;
+        }
+        let current = Vec2::new(position.x, position.y);
+        let Some(resolved) = map.push_out(current, collider.radius) else {
+            continue;
+        };
+
+        let push = resolved - current;
+        position.x = resolved.x;
+        position.y = resolved.y;
+        if let Some(mut velocity) = velocity {
+            if push.x * velocity.x < 0.0 {
+                velocity.x = 0.0;
+            }
+            if push.y * velocity.y < 0.0 {
+                velocity.y = 0.0;
+            }
+        }
+    }
+}
+
+// Moves newly spawned bodies out of walls to the nearest open tile; sensors stay where they were placed
+pub fn relocate_blocked_spawns_system(
+    map: Res<WorldMap>,
+    mut query: Query<(&mut Position, &Collider, &ZoneId), Added<Collider>>,
+) {
+    for (mut position, collider, zone) in query.iter_mut() {
+        if *zone != MAIN_ZONE || collider.sensor {
+            continue;
+        }
+        let current = Vec2::new(position.x, position.y);
+        if !map.overlaps_blocked(current, collider.radius) {
+            continue;
+        }
+
+        if let Some(open) = find_open_tile(&map, current, collider.radius) {
+            position.x = open.x;
+            position.y = open.y;
+        }
+    }
+}
+
+// Breadth-first search outward from the blocked tile for the closest open one
+pub fn find_open_tile(map: &WorldMap, from: Vec2, radius: f32) -> Option<Vec2> {
+    let start = (map.tile_of(from.x), map.tile_of(from.y));
+    let mut visited = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some((x, y)) = queue.pop_front() {
+        let center = map.tile_center(x, y);
+        if !map.overlaps_blocked(center, radius) {
+            return Some(center);
+        }
+
+        for (next_x, next_y) in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            let inside = next_x >= 0 && next_y >= 0 && next_x < map.width as i32 && next_y < map.height as i32;
+            if inside && visited.insert((next_x, next_y)) {
+                queue.push_back((next_x, next_y));
+            }
+        }
+    }
+
+    None
+}
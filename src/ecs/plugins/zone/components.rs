@@ -0,0 +1,237 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use crate::ecs::components::{GameConfig, Position};
+use crate::ecs::plugins::ai::components::NpcSpawn;
+use crate::ecs::plugins::collision::components::{Collider, COLLISION_LAYER_PLAYER, COLLISION_LAYER_PORTAL};
+use crate::ecs::plugins::network::components::{NetworkEntityKind, NetworkedEntityBundle};
+
+// ============================================================================
+// ZONE DATA
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PortalDefinition {
+    pub position: Vec2,
+    #[serde(default = "default_portal_radius")]
+    pub radius: f32,
+    // Name of the destination zone
+    pub to: String,
+    // Where the player arrives; a spawn point of the destination when omitted
+    #[serde(default)]
+    pub arrival: Option<Vec2>,
+}
+
+fn default_portal_radius() -> f32 {
+    24.0
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ZoneDefinition {
+    pub name: String,
+    // Zones without bounds share the main world's bounds (GameConfig / world map)
+    #[serde(default)]
+    pub bounds: Option<Vec2>,
+    #[serde(default)]
+    pub spawn_points: Vec<Vec2>,
+    #[serde(default)]
+    pub portals: Vec<PortalDefinition>,
+    // A template only: every party (or solo player) entering it gets a private copy
+    #[serde(default)]
+    pub instanced: bool,
+    // NPCs spawned into each instance of an instanced template
+    #[serde(default)]
+    pub npcs: Vec<NpcSpawn>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ZoneDataFile {
+    pub zones: Vec<ZoneDefinition>,
+}
+
+// ============================================================================
+// ZONE COMPONENTS
+// ============================================================================
+
+// Index into Zones, or the ID of an instance. Entities only see, touch and hit
+// entities in their own zone.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct ZoneId(pub u32);
+
+// Players join the first zone, and the world map only covers this zone
+pub const MAIN_ZONE: ZoneId = ZoneId(0);
+
+// Moves players who step inside `radius` to another zone
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Portal {
+    pub destination: ZoneId,
+    pub arrival: Option<Vec2>,
+    pub radius: f32,
+}
+
+// On a player who arrived standing in a portal, which stays inert for them until they
+// step out, so arriving on a return portal doesn't bounce them straight back
+#[derive(Component, Debug)]
+pub struct PortalArrival;
+
+// Zone a returning player was saved in, set by snapshot restore and profile loading
+// (the profile goes last, so it wins) and applied once spawning is done
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SavedZone {
+    pub zone: ZoneId,
+    pub position: Vec2,
+}
+
+// A static sensor that only players trigger; portals never change, so everything lives in the snapshot
+#[derive(Bundle)]
+pub struct PortalBundle {
+    pub portal: Portal,
+    pub position: Position,
+    pub zone: ZoneId,
+    pub collider: Collider,
+    pub networked: NetworkedEntityBundle,
+}
+
+impl PortalBundle {
+    pub fn new(portal: Portal, destination_name: &str, position: Vec2, zone: ZoneId, network_id: u32) -> Self {
+        let mut networked = NetworkedEntityBundle::new(network_id, NetworkEntityKind::Portal);
+        networked.snapshot.components.insert(DESTINATION_KEY.to_string(), serde_json::Value::String(destination_name.to_string()));
+        networked.snapshot.components.insert(RADIUS_KEY.to_string(), serde_json::Value::from(portal.radius));
+
+        Self {
+            portal,
+            position: Position { x: position.x, y: position.y },
+            zone,
+            collider: Collider::sensor(portal.radius, COLLISION_LAYER_PORTAL, COLLISION_LAYER_PLAYER),
+            networked,
+        }
+    }
+}
+
+// ============================================================================
+// INSTANCES
+// ============================================================================
+
+// Who an instance belongs to; party members share their party's instance. Player IDs
+// are connection slots that get reused, so solo players are keyed on their account, or
+// for guests on their network ID, which is never reused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstanceOwner {
+    Party(u32),
+    Account(String),
+    Guest(u32),
+}
+
+#[derive(Debug, Clone)]
+pub struct ZoneInstance {
+    pub template: ZoneId,
+    pub owner: InstanceOwner,
+    // Tick the last player left, cleared when someone is inside again
+    pub empty_since: Option<u64>,
+}
+
+// ============================================================================
+// ZONE EVENTS
+// ============================================================================
+
+// Moves a player to another zone, at `position` or one of the zone's spawn points.
+// Sent by portals, and for returning players saved outside the main zone.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ZoneTransferEvent {
+    pub player_id: u32,
+    pub zone: ZoneId,
+    pub position: Option<Vec2>,
+}
+
+// ============================================================================
+// ZONE RESOURCES
+// ============================================================================
+
+#[derive(Resource, Debug, Clone)]
+pub struct Zones {
+    pub zones: Vec<ZoneDefinition>,
+    // Instances take their ID from NetworkIdAllocator, so it never collides with a
+    // template index or another instance
+    pub instances: BTreeMap<ZoneId, ZoneInstance>,
+}
+
+impl Default for Zones {
+    fn default() -> Self {
+        Self {
+            zones: vec![ZoneDefinition {
+                name: "main".to_string(),
+                bounds: None,
+                spawn_points: Vec::new(),
+                portals: Vec::new(),
+                instanced: false,
+                npcs: Vec::new(),
+            }],
+            instances: BTreeMap::new(),
+        }
+    }
+}
+
+impl Zones {
+    // Instances resolve to their template
+    pub fn get(&self, zone: ZoneId) -> Option<&ZoneDefinition> {
+        let template = self.instances.get(&zone).map_or(zone, |instance| instance.template);
+        self.zones.get(template.0 as usize)
+    }
+
+    pub fn instance_of(&self, owner: &InstanceOwner, template: ZoneId) -> Option<ZoneId> {
+        self.instances.iter()
+            .find(|(_, instance)| instance.owner == *owner && instance.template == template)
+            .map(|(zone, _)| *zone)
+    }
+
+    pub fn find(&self, name: &str) -> Option<ZoneId> {
+        self.zones.iter().position(|zone| zone.name == name).map(|index| ZoneId(index as u32))
+    }
+
+    // The zone named in a saved record; records from before zones were saved are in the main zone
+    pub fn find_saved(&self, name: Option<&str>) -> Option<ZoneId> {
+        name.map_or(Some(MAIN_ZONE), |name| self.find(name))
+    }
+
+    pub fn name(&self, zone: ZoneId) -> &str {
+        self.get(zone).map_or("", |zone| zone.name.as_str())
+    }
+
+    // Whether a body of this radius at `position` touches one of the zone's portals
+    pub fn touches_portal(&self, zone: ZoneId, position: Vec2, radius: f32) -> bool {
+        self.get(zone).is_some_and(|zone| {
+            zone.portals.iter().any(|portal| portal.position.distance(position) < portal.radius + radius)
+        })
+    }
+
+    pub fn bounds(&self, zone: ZoneId, game_config: &GameConfig) -> Vec2 {
+        self.get(zone).and_then(|zone| zone.bounds).unwrap_or(game_config.world_bounds)
+    }
+
+    // One of the zone's spawn points, or None when it has none
+    pub fn spawn_point(&self, zone: ZoneId, rng: &mut impl Rng) -> Option<Vec2> {
+        let points = &self.get(zone)?.spawn_points;
+        match points.len() {
+            0 => None,
+            1 => Some(points[0]),
+            count => Some(points[rng.gen_range(0..count)]),
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct ZoneConfig {
+    // How long an instance stays up with nobody inside
+    pub instance_timeout_ticks: u64,
+}
+
+// ============================================================================
+// ZONE MESSAGE KEYS
+// ============================================================================
+
+pub const ZONE_NAME_KEY: &str = "zn";
+pub const BOUNDS_KEY: &str = "b";
+pub const DESTINATION_KEY: &str = "to";
+pub const RADIUS_KEY: &str = "rd";
+pub const INSTANCE_KEY: &str = "in";
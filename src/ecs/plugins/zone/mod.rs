@@ -0,0 +1,71 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+use components::{ZoneConfig, ZoneDataFile, ZoneTransferEvent, Zones};
+use systems::{instance_cleanup_system, portal_system, restore_saved_zone_system, spawn_portals_system, zone_transfer_system};
+use crate::ecs::plugins::simulation::components::TICK_RATE_HZ;
+
+const DEFAULT_ZONE_DATA_PATH: &str = "data/zones/zones.json";
+const DEFAULT_INSTANCE_TIMEOUT_SECS: f64 = 60.0;
+
+// Zone plugin: independent map regions in one world, each with its own bounds, spawn
+// points and interest management, connected by portals. Instanced zones are private
+// copies created on demand and torn down once left empty.
+pub struct ZonePlugin {
+    pub data_path: String,
+    pub instance_timeout_secs: f64,
+}
+
+impl ZonePlugin {
+    // ZONE_DATA overrides the default zone file; INSTANCE_EMPTY_TIMEOUT_SECS sets how
+    // long an empty instance is kept
+    pub fn from_env() -> Self {
+        let data_path = std::env::var("ZONE_DATA").unwrap_or_else(|_| DEFAULT_ZONE_DATA_PATH.to_string());
+        let instance_timeout_secs = std::env::var("INSTANCE_EMPTY_TIMEOUT_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_INSTANCE_TIMEOUT_SECS);
+
+        Self { data_path, instance_timeout_secs }
+    }
+}
+
+impl Plugin for ZonePlugin {
+    fn build(&self, app: &mut App) {
+        let data = std::fs::read_to_string(&self.data_path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| serde_json::from_str::<ZoneDataFile>(&data).map_err(|e| e.to_string()));
+
+        // Everything else assumes the main zone exists, so an empty file counts as a failure
+        let zones = match data {
+            Ok(data) if !data.zones.is_empty() => {
+                println!("🗺️ Loaded {} zones from {}", data.zones.len(), self.data_path);
+                Zones { zones: data.zones, instances: Default::default() }
+            }
+            Ok(_) => {
+                println!("❌ Zone data {} has no zones (running a single zone)", self.data_path);
+                Zones::default()
+            }
+            Err(e) => {
+                println!("❌ Failed to load zone data {}: {} (running a single zone)", self.data_path, e);
+                Zones::default()
+            }
+        };
+
+        app.insert_resource(zones)
+            .insert_resource(ZoneConfig {
+                instance_timeout_ticks: (self.instance_timeout_secs * TICK_RATE_HZ).round() as u64,
+            })
+            .add_event::<ZoneTransferEvent>()
+            .add_systems(PostStartup, spawn_portals_system)
+            .add_systems(FixedUpdate, (restore_saved_zone_system, portal_system, zone_transfer_system, instance_cleanup_system).chain()
+                .after(crate::ecs::systems::boundary_system)
+                .after(crate::ecs::plugins::collision::systems::collision_resolution_system)
+                .after(crate::ecs::systems::player_spawn_system)
+                .after(crate::ecs::systems::character_spawn_system)
+                .after(crate::ecs::plugins::party::systems::party_visibility_system)
+                .after(crate::ecs::plugins::health::systems::player_respawn_system)
+                .before(crate::ecs::plugins::network::systems::detect_position_changes_system));
+    }
+}
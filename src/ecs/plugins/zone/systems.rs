@@ -0,0 +1,274 @@
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+use crate::ecs::components::*;
+use crate::ecs::plugins::collision::components::{Collider, TriggerEnterEvent, TriggerExitEvent};
+use crate::ecs::plugins::health::components::Dead;
+use crate::ecs::plugins::network::components::{
+    EntityUpdate, NetworkEntityKind, NetworkId, NetworkIdAllocator, NetworkMessage, NetworkUpdates, NetworkedEntityBundle,
+    ViewRangeTracker, POSITION_KEY, ZONE_CHANGE_TYPE,
+};
+use crate::ecs::plugins::party::components::Parties;
+use crate::ecs::plugins::simulation::components::{SimulationRng, SimulationTick};
+use super::components::*;
+
+type TransferringPlayerQuery<'w, 's> = Query<'w, 's, (
+    &'static Player,
+    &'static NetworkId,
+    Option<&'static Account>,
+    &'static Collider,
+    &'static mut ZoneId,
+    &'static mut Position,
+    &'static mut Velocity,
+    &'static mut DesiredVelocity,
+)>;
+
+// ============================================================================
+// ZONE HELPERS
+// ============================================================================
+
+// Spawns a zone's portals; an instance gets a copy of its template's
+fn spawn_zone_portals(commands: &mut Commands, zones: &Zones, allocator: &mut NetworkIdAllocator, zone: ZoneId) -> usize {
+    let Some(template) = zones.get(zone) else {
+        return 0;
+    };
+    let mut spawned = 0;
+    for definition in &template.portals {
+        let Some(destination) = zones.find(&definition.to) else {
+            println!("❌ Portal in zone '{}' leads to unknown zone '{}'", template.name, definition.to);
+            continue;
+        };
+        let portal = Portal { destination, arrival: definition.arrival, radius: definition.radius };
+        commands.spawn(PortalBundle::new(portal, &definition.to, definition.position, zone, allocator.allocate()));
+        spawned += 1;
+    }
+    spawned
+}
+
+// Spawns an instance's NPCs from its template. They take their network ID as character
+// ID too, which never collides with the AI data file's sequential IDs.
+fn spawn_instance_npcs(
+    commands: &mut Commands,
+    zones: &Zones,
+    allocator: &mut NetworkIdAllocator,
+    game_config: &GameConfig,
+    rng: &mut impl Rng,
+    zone: ZoneId,
+) {
+    let Some(template) = zones.get(zone) else {
+        return;
+    };
+    for spawn in &template.npcs {
+        for _ in 0..spawn.count {
+            let network_id = allocator.allocate();
+            let position = Position { x: spawn.position[0], y: spawn.position[1] };
+            let mut bundle = CharacterBundle::new(network_id, Some(position), game_config, rng);
+            bundle.zone = zone;
+
+            let mut character = commands.spawn((bundle, NpcArchetype { name: spawn.archetype.clone() }));
+            if spawn.networked {
+                character.insert(NetworkedEntityBundle::new(network_id, NetworkEntityKind::Npc));
+            }
+        }
+    }
+}
+
+// ============================================================================
+// ZONE SYSTEMS
+// ============================================================================
+
+// Portals get network IDs, so they are spawned after a snapshot has reserved its IDs.
+// Instance templates are never entered directly, so their portals come with each instance.
+pub fn spawn_portals_system(
+    mut commands: Commands,
+    zones: Res<Zones>,
+    mut allocator: ResMut<NetworkIdAllocator>,
+) {
+    let mut spawned = 0;
+    for (index, zone) in zones.zones.iter().enumerate() {
+        if !zone.instanced {
+            spawned += spawn_zone_portals(&mut commands, &zones, &mut allocator, ZoneId(index as u32));
+        }
+    }
+    if spawned > 0 {
+        println!("🌀 Spawned {} portals", spawned);
+    }
+}
+
+// Living players stepping into a portal's sensor are sent to its destination, unless
+// they arrived inside it and haven't stepped out yet
+pub fn portal_system(
+    mut commands: Commands,
+    mut enter_events: EventReader<TriggerEnterEvent>,
+    mut exit_events: EventReader<TriggerExitEvent>,
+    mut transfer_events: EventWriter<ZoneTransferEvent>,
+    portals: Query<(&Portal, &ZoneId)>,
+    players: Query<(&Player, &ZoneId, Has<PortalArrival>), Without<Dead>>,
+) {
+    // Leaving the portal they came from after a transfer doesn't count
+    for event in exit_events.read() {
+        if let (Ok((_, portal_zone)), Ok((_, zone, true))) = (portals.get(event.sensor), players.get(event.other))
+            && portal_zone == zone
+        {
+            commands.entity(event.other).remove::<PortalArrival>();
+        }
+    }
+
+    for event in enter_events.read() {
+        let (Ok((portal, _)), Ok((player, _, arrived_inside))) = (portals.get(event.sensor), players.get(event.other)) else {
+            continue;
+        };
+        if !arrived_inside {
+            transfer_events.send(ZoneTransferEvent { player_id: player.id, zone: portal.destination, position: portal.arrival });
+        }
+    }
+}
+
+// Sends returning players back to the zone they were saved in. Instances don't survive
+// a restart, so a player saved in one starts a fresh instance at a spawn point.
+pub fn restore_saved_zone_system(
+    mut commands: Commands,
+    zones: Res<Zones>,
+    mut transfer_events: EventWriter<ZoneTransferEvent>,
+    players: Query<(Entity, &Player, &ZoneId, &SavedZone)>,
+) {
+    for (entity, player, zone, saved) in players.iter() {
+        commands.entity(entity).remove::<SavedZone>();
+        if saved.zone == *zone {
+            continue;
+        }
+        let instanced = zones.get(saved.zone).is_some_and(|definition| definition.instanced);
+        transfer_events.send(ZoneTransferEvent {
+            player_id: player.id,
+            zone: saved.zone,
+            position: (!instanced).then_some(saved.position),
+        });
+    }
+}
+
+// Moves the player and forgets everything they could see. The client gets a zone
+// change message, then proximity_detection_system sends full syncs of what is in
+// view in the new zone later this tick. Sending a player to an instanced template
+// puts them in their party's instance, created on first entry.
+#[allow(clippy::too_many_arguments)]
+pub fn zone_transfer_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    mut zones: ResMut<Zones>,
+    game_config: Res<GameConfig>,
+    player_registry: Res<PlayerRegistry>,
+    parties: Res<Parties>,
+    mut allocator: ResMut<NetworkIdAllocator>,
+    mut rng: ResMut<SimulationRng>,
+    mut network_updates: ResMut<NetworkUpdates>,
+    mut transfer_events: EventReader<ZoneTransferEvent>,
+    mut players: TransferringPlayerQuery,
+    mut view_trackers: Query<&mut ViewRangeTracker>,
+) {
+    for event in transfer_events.read() {
+        let Some(definition) = zones.get(event.zone) else {
+            println!("❌ Zone transfer of player {} to unknown zone {}", event.player_id, event.zone.0);
+            continue;
+        };
+        let instanced = definition.instanced;
+        let Some(player_entity) = player_registry.get_player_entity(event.player_id) else {
+            continue;
+        };
+        let Ok((player, network_id, account, collider, mut zone, mut position, mut velocity, mut desired_velocity)) = players.get_mut(player_entity) else {
+            continue;
+        };
+
+        let mut destination = event.zone;
+        if instanced && !zones.instances.contains_key(&destination) {
+            let owner = match (parties.party_of(player.id), account) {
+                (Some((party_id, _)), _) => InstanceOwner::Party(party_id),
+                (None, Some(account)) => InstanceOwner::Account(account.id.clone()),
+                (None, None) => InstanceOwner::Guest(network_id.0),
+            };
+            destination = match zones.instance_of(&owner, event.zone) {
+                Some(instance) => instance,
+                None => {
+                    let instance = ZoneId(allocator.allocate());
+                    println!("🏰 Created instance {} of '{}' for {:?}", instance.0, zones.name(event.zone), owner);
+                    zones.instances.insert(instance, ZoneInstance { template: event.zone, owner, empty_since: None });
+                    spawn_zone_portals(&mut commands, &zones, &mut allocator, instance);
+                    spawn_instance_npcs(&mut commands, &zones, &mut allocator, &game_config, &mut **rng, instance);
+                    instance
+                }
+            };
+        }
+
+        let bounds = zones.bounds(destination, &game_config);
+        let arrival = event.position
+            .or_else(|| zones.spawn_point(destination, &mut **rng))
+            .unwrap_or(bounds / 2.0)
+            .clamp(Vec2::ZERO, bounds);
+        println!("🌀 Player {} moved from zone '{}' to '{}'", player.id, zones.name(*zone), zones.name(destination));
+
+        *zone = destination;
+        *position = Position { x: arrival.x, y: arrival.y };
+        *velocity = Velocity { x: 0.0, y: 0.0 };
+        *desired_velocity = DesiredVelocity::default();
+        if zones.touches_portal(destination, arrival, collider.radius) {
+            commands.entity(player_entity).insert(PortalArrival);
+        } else {
+            commands.entity(player_entity).remove::<PortalArrival>();
+        }
+
+        for mut view_tracker in view_trackers.iter_mut() {
+            view_tracker.players_in_view.remove(&player.id);
+            view_tracker.distant_players.remove(&player.id);
+        }
+
+        let mut components = HashMap::new();
+        components.insert(ZONE_NAME_KEY.to_string(), serde_json::Value::String(zones.name(destination).to_string()));
+        if zones.instances.contains_key(&destination) {
+            components.insert(INSTANCE_KEY.to_string(), serde_json::Value::from(destination.0));
+        }
+        components.insert(BOUNDS_KEY.to_string(), serde_json::json!([bounds.x, bounds.y]));
+        components.insert(POSITION_KEY.to_string(), serde_json::json!([arrival.x.round(), arrival.y.round()]));
+        let message = NetworkMessage {
+            message_type: ZONE_CHANGE_TYPE.to_string(),
+            entity_updates: vec![EntityUpdate { network_id: network_id.0, components }],
+            tick: Some(tick.0),
+        };
+        network_updates.player_messages.entry(player.id).or_default().push(message);
+    }
+}
+
+// Tears down instances that have been empty for the timeout, with everything left inside
+pub fn instance_cleanup_system(
+    mut commands: Commands,
+    tick: Res<SimulationTick>,
+    config: Res<ZoneConfig>,
+    mut zones: ResMut<Zones>,
+    players: Query<&ZoneId, With<Player>>,
+    entities: Query<(Entity, &ZoneId), Without<Player>>,
+) {
+    if zones.instances.is_empty() {
+        return;
+    }
+
+    let occupied: HashSet<ZoneId> = players.iter().copied().collect();
+    let mut expired = Vec::new();
+    for (zone, instance) in zones.instances.iter_mut() {
+        if occupied.contains(zone) {
+            instance.empty_since = None;
+            continue;
+        }
+        let empty_since = *instance.empty_since.get_or_insert(tick.0);
+        if tick.0 - empty_since >= config.instance_timeout_ticks {
+            expired.push(*zone);
+        }
+    }
+
+    for zone in expired {
+        println!("🏚️ Closed empty instance {} of '{}'", zone.0, zones.name(zone));
+        zones.instances.remove(&zone);
+        for (entity, entity_zone) in entities.iter() {
+            if *entity_zone == zone {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
@@ -1,15 +1,15 @@
 use bevy::prelude::*;
 use crate::ecs::components::*;
+use crate::ecs::plugins::health::components::Dead;
+use crate::ecs::plugins::simulation::components::SimulationRng;
 
 // ============================================================================
 // INPUT SYSTEMS
 // ============================================================================
 
-const MAX_INPUT_MAGNITUDE: f32 = 1.1;
-
 pub fn input_processing_system(
     mut input_events: EventReader<InputCommandEvent>,
-    mut query: Query<(&Player, &mut DesiredVelocity, &CharacterProfile)>,
+    mut query: Query<(&Player, &mut DesiredVelocity, &CharacterProfile), Without<Dead>>,
 ) {
     for event in input_events.read() {
         for (player, mut desired_velocity, profile) in query.iter_mut() {
@@ -24,6 +24,10 @@ pub fn input_processing_system(
                         desired_velocity.x = 0.0;
                         desired_velocity.y = 0.0;
                     }
+                    // Casts are validated and resolved by the ability plugin, acks by lag compensation,
+                    // chat, parties and inventory by their plugins
+                    InputCommand::CastAtTarget { .. } | InputCommand::CastInDirection { .. } | InputCommand::Ack { .. }
+                    | InputCommand::Chat { .. } | InputCommand::Party(_) | InputCommand::Inventory(_) => {}
                 }
                 break;
             }
@@ -36,15 +40,15 @@ pub fn input_processing_system(
 // ============================================================================
 
 const MIN_VELOCITY_THRESHOLD: f32 = 0.01;
-const MIN_CHANGE_THRESHOLD: f32 = 0.01;
 const WORLD_MIN_X: f32 = 0.0;
 const WORLD_MIN_Y: f32 = 0.0;
 
 pub fn acceleration_friction_system(
-    time: Res<Time>,
+    time: Res<Time<Fixed>>,
     mut query: Query<(&mut Velocity, &DesiredVelocity, &CharacterProfile, &Friction)>,
 ) {
-    let dt = time.delta_secs();
+    // Always step by the fixed timestep so results don't depend on frame timing
+    let dt = time.timestep().as_secs_f32();
     
     for (mut velocity, desired_velocity, profile, friction) in query.iter_mut() {
         let is_trying_to_move = desired_velocity.x.abs() > MIN_VELOCITY_THRESHOLD 
@@ -88,10 +92,10 @@ pub fn acceleration_friction_system(
 }
 
 pub fn movement_system(
-    time: Res<Time>,
+    time: Res<Time<Fixed>>,
     mut query: Query<(&mut Position, &Velocity)>,
 ) {
-    let dt = time.delta_secs();
+    let dt = time.timestep().as_secs_f32();
     
     for (mut position, velocity) in query.iter_mut() {
         // Skip position updates if velocity is effectively zero
@@ -104,18 +108,22 @@ pub fn movement_system(
     }
 }
 
+// Each zone has its own bounds
 pub fn boundary_system(
-    mut query: Query<(&mut Position, &mut Velocity), Changed<Position>>,
+    mut query: Query<(&mut Position, &mut Velocity, &crate::ecs::plugins::zone::components::ZoneId), Changed<Position>>,
     config: Res<GameConfig>,
+    zones: Res<crate::ecs::plugins::zone::components::Zones>,
 ) {
-    for (mut position, mut velocity) in query.iter_mut() {
+    for (mut position, mut velocity, zone) in query.iter_mut() {
+        let bounds = zones.bounds(*zone, &config);
+        
         if position.x < WORLD_MIN_X {
             position.x = WORLD_MIN_X;
             velocity.x = -velocity.x;
         }
         
-        if position.x > config.world_bounds.x {
-            position.x = config.world_bounds.x;
+        if position.x > bounds.x {
+            position.x = bounds.x;
             velocity.x = -velocity.x;
         }
         
@@ -124,8 +132,8 @@ pub fn boundary_system(
             velocity.y = -velocity.y;
         }
         
-        if position.y > config.world_bounds.y {
-            position.y = config.world_bounds.y;
+        if position.y > bounds.y {
+            position.y = bounds.y;
             velocity.y = -velocity.y;
         }
     }
@@ -136,23 +144,37 @@ pub fn boundary_system(
 // PLAYER MANAGEMENT SYSTEMS
 // ============================================================================
 
+#[allow(clippy::too_many_arguments)]
 pub fn player_spawn_system(
     mut commands: Commands,
     mut spawn_events: EventReader<PlayerSpawnEvent>,
     mut player_registry: ResMut<PlayerRegistry>,
     mut allocator: ResMut<crate::ecs::plugins::network::components::NetworkIdAllocator>,
     game_config: Res<GameConfig>,
-    connections: Res<crate::ecs::plugins::websocket::components::WebSocketConnections>,
+    mut rng: ResMut<SimulationRng>,
+    mut network_updates: ResMut<crate::ecs::plugins::network::components::NetworkUpdates>,
+    world_map: Option<Res<crate::ecs::plugins::world_map::components::WorldMap>>,
+    zones: Res<crate::ecs::plugins::zone::components::Zones>,
 ) {
     for event in spawn_events.read() {
         println!("🎮 Spawning player {}", event.player_id);
         
+        // Players join the main zone, at one of its spawn points if it has any
+        let mut bundle = PlayerBundle::new(event.player_id, &game_config, &mut **rng);
+        if let Some(point) = zones.spawn_point(crate::ecs::plugins::zone::components::MAIN_ZONE, &mut **rng) {
+            bundle.position = Position { x: point.x, y: point.y };
+        }
+        
         // Spawn player entity with networking
         let network_id = allocator.allocate();
-        let player_entity = commands.spawn((
-            PlayerBundle::new(event.player_id, &game_config),
-            crate::ecs::plugins::network::components::NetworkedEntityBundle::new(network_id),
-        )).id();
+        let mut player = commands.spawn((
+            bundle,
+            crate::ecs::plugins::network::components::NetworkedEntityBundle::new(network_id, crate::ecs::plugins::network::components::NetworkEntityKind::Player),
+        ));
+        if let Some(account) = &event.account {
+            player.insert(Account { id: account.clone() });
+        }
+        let player_entity = player.id();
         
         // Register player
         player_registry.register_player(event.player_id, player_entity);
@@ -166,12 +188,19 @@ pub fn player_spawn_system(
                     let mut components = std::collections::HashMap::new();
                     components.insert("player_id".to_string(), serde_json::Value::Number(serde_json::Number::from(event.player_id)));
                     components.insert("network_id".to_string(), serde_json::Value::Number(serde_json::Number::from(network_id)));
+                    components.insert("zone".to_string(), serde_json::Value::String(zones.name(crate::ecs::plugins::zone::components::MAIN_ZONE).to_string()));
+                    // Map hash is sent as hex since JSON numbers can't hold a full u64
+                    if let Some(map) = &world_map {
+                        components.insert("map_id".to_string(), serde_json::Value::String(map.id.clone()));
+                        components.insert("map_hash".to_string(), serde_json::Value::String(format!("{:016x}", map.hash)));
+                    }
                     components
                 },
             }],
+            tick: None,
         };
         
-        let _ = connections.player_network_sender.send((event.player_id, welcome_msg));
+        network_updates.player_messages.entry(event.player_id).or_default().push(welcome_msg);
         
         println!("✅ Player {} spawned with network ID {}", event.player_id, network_id);
     }
@@ -183,10 +212,9 @@ pub fn player_despawn_system(
     mut player_registry: ResMut<PlayerRegistry>,
 ) {
     for event in despawn_events.read() {
-        println!("👋 Despawning player {}", event.player_id);
-        
-        // Despawn player entity
+        // Players handed to another shard are despawned before their connection closes
         if let Some(entity) = player_registry.unregister_player(event.player_id) {
+            println!("👋 Despawning player {}", event.player_id);
             commands.entity(entity).despawn();
         }
     }
@@ -196,14 +224,26 @@ pub fn character_spawn_system(
     mut commands: Commands,
     mut spawn_events: EventReader<CharacterSpawnEvent>,
     game_config: Res<GameConfig>,
+    mut rng: ResMut<SimulationRng>,
+    mut allocator: ResMut<crate::ecs::plugins::network::components::NetworkIdAllocator>,
 ) {
     for event in spawn_events.read() {
         println!("🤖 Spawning character {}", event.character_id);
         
-        // Spawn character entity (no networking for bots/NPCs)
-        let _character_entity = commands.spawn(
-            CharacterBundle::new(event.character_id, event.position, &game_config)
-        ).id();
+        // Spawn character entity, networked only if clients should see it
+        let mut character = commands.spawn(
+            CharacterBundle::new(event.character_id, event.position, &game_config, &mut **rng)
+        );
+        if event.networked {
+            let network_id = allocator.allocate();
+            character.insert(crate::ecs::plugins::network::components::NetworkedEntityBundle::new(
+                network_id,
+                crate::ecs::plugins::network::components::NetworkEntityKind::Npc,
+            ));
+        }
+        if let Some(archetype) = &event.archetype {
+            character.insert(NpcArchetype { name: archetype.clone() });
+        }
         
         println!("✅ Character {} spawned", event.character_id);
     }
@@ -228,3 +268,43 @@ pub fn character_despawn_system(
 }
 
 
+// ============================================================================
+// SHUTDOWN SYSTEMS
+// ============================================================================
+
+// Listen for Ctrl+C / SIGTERM on a dedicated thread so the app can exit cleanly
+pub fn setup_shutdown_listener(signal: Res<ShutdownSignal>) {
+    let sender = signal.sender.clone();
+    
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            #[cfg(unix)]
+            {
+                let mut terminate = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()).unwrap();
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+            }
+            #[cfg(not(unix))]
+            let _ = tokio::signal::ctrl_c().await;
+            
+            let _ = sender.send(());
+        });
+    });
+}
+
+// Turn a received shutdown signal into AppExit so exit hooks (snapshots) can run
+pub fn shutdown_signal_system(
+    signal: Res<ShutdownSignal>,
+    mut exit_events: EventWriter<AppExit>,
+) {
+    if signal.receiver.try_recv().is_ok() {
+        println!("🛑 Shutdown signal received, stopping server...");
+        exit_events.send(AppExit::Success);
+    }
+}
@@ -33,13 +33,15 @@ Run the server and connect clients to `ws://localhost:5000` for WebSocket
 or UDP on the configured port.
 */
 
+use bevy::app::ScheduleRunnerPlugin;
 use bevy::prelude::*;
+use std::time::Duration;
 
 mod ecs;
 
 use ecs::components::*;
 use ecs::systems::*;
-use ecs::{WebSocketPlugin, NetworkPlugin};
+use ecs::{WebSocketPlugin, NetworkPlugin, SimulationPlugin, ReplayPlugin, SnapshotPlugin, PersistencePlugin, CollisionPlugin, WorldMapPlugin, PathfindingPlugin, AiPlugin, HealthPlugin, AbilityPlugin, ProjectilePlugin, LagCompensationPlugin, AntiCheatPlugin, ChatPlugin, PartyPlugin, InventoryPlugin, LootPlugin, ZonePlugin, ShardPlugin};
 
 // Core game modules
 /// Main entry point for the MMO game server.
@@ -50,53 +52,121 @@ fn main() {
     println!("🚀 Starting MMO Game Server...");
     println!("📡 Network Protocol: WebSocket");
     
-    App::new()
+    let replay = ReplayPlugin::from_env();
+    let playback = replay.as_ref().is_some_and(|replay| replay.is_playback());
+    
+    let mut simulation = SimulationPlugin::from_env();
+    if let Some(replay) = &replay {
+        replay.configure_simulation(&mut simulation);
+    }
+    
+    // Deterministic mode runs exactly one fixed tick per frame, paced at the tick rate.
+    // Playback is headless and runs ticks as fast as possible.
+    let frame_wait = if simulation.deterministic && !playback {
+        SimulationPlugin::timestep()
+    } else {
+        Duration::ZERO
+    };
+    
+    let mut app = App::new();
+    app
         // Bevy's minimal plugins (no graphics/audio needed for server)
-        .add_plugins(MinimalPlugins)
+        .add_plugins(MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(frame_wait)))
         
         // Add plugins
-        .add_plugins(NetworkPlugin)
-        .add_plugins(WebSocketPlugin::default())
-        
+        .add_plugins(simulation)
+        .add_plugins(NetworkPlugin::from_env())
+        .add_plugins(CollisionPlugin)
+        .add_plugins(WorldMapPlugin::from_env())
+        .add_plugins(PathfindingPlugin)
+        .add_plugins(HealthPlugin::from_env())
+        .add_plugins(AbilityPlugin::from_env())
+        .add_plugins(ProjectilePlugin)
+        .add_plugins(LagCompensationPlugin::from_env())
+        .add_plugins(ChatPlugin::from_env())
+        .add_plugins(PartyPlugin::from_env())
+        .add_plugins(InventoryPlugin::from_env())
+        .add_plugins(LootPlugin::from_env())
+        .add_plugins(ZonePlugin::from_env());
+    
+    // Playback feeds recorded events instead of accepting WebSocket clients
+    if !playback {
+        app.add_plugins(WebSocketPlugin::from_env())
+            .add_plugins(AntiCheatPlugin::from_env());
+        if let Some(shard) = ShardPlugin::from_env() {
+            app.add_plugins(shard);
+        }
+    }
+    
+    app.add_plugins(AiPlugin {
+        spawn_initial_npcs: !playback,
+        ..AiPlugin::from_env()
+    });
+    
+    app
         // Add resources
         .insert_resource(GameConfig::default())
         .insert_resource(PlayerRegistry::default())
-        .insert_resource(Time::<Fixed>::from_hz(10.0))
+        .insert_resource(ShutdownSignal::default())
         
         // Add events
+        .add_event::<ClientInputEvent>()
         .add_event::<InputCommandEvent>()
         .add_event::<PlayerSpawnEvent>()
         .add_event::<PlayerDespawnEvent>()
+        .add_event::<PlayerKickEvent>()
+        .add_event::<PlayerRedirectEvent>()
+        .add_event::<PlayerHandoffArrivalEvent>()
         .add_event::<CharacterSpawnEvent>()
         .add_event::<CharacterDespawnEvent>()
         
         // Add systems
+        // Systems drawing from SimulationRng are explicitly ordered so a seed always gives the
+        // same world: player spawns, character spawns, AI, loot drops, respawns, zone transfers
         .add_systems(FixedUpdate, (
             // Player management systems
             player_spawn_system,
             player_despawn_system,
             
             // Character management systems
-            character_spawn_system,
+            character_spawn_system.after(player_spawn_system),
             character_despawn_system,
             
             // Input systems
             input_processing_system,
             
-            // Movement systems
+            // Movement systems (ordered after input so replays apply commands on the same tick)
             (
                 acceleration_friction_system,
                 movement_system,
                 boundary_system
-            ).chain()
+            ).chain().after(input_processing_system)
             
         ))
         
-        // Setup game world when server starts
-        .add_systems(Startup, setup_game_world)
+        // Stop cleanly on Ctrl+C / SIGTERM
+        .add_systems(Update, shutdown_signal_system)
         
-        // Start the game loop
-        .run();
+        // Setup game world when server starts
+        .add_systems(Startup, (setup_game_world, setup_shutdown_listener));
+    
+    // Replay systems read events registered above, so the plugin is added last.
+    // Persistence is skipped while replaying since restored state would break determinism.
+    if let Some(replay) = replay {
+        app.add_plugins(replay);
+    } else {
+        if let Some(snapshot) = SnapshotPlugin::from_env() {
+            app.add_plugins(snapshot);
+        }
+        if let Some(persistence) = PersistencePlugin::from_env() {
+            app.add_plugins(persistence);
+        }
+    }
+    
+    // Start the game loop (a diverged replay exits with a non-zero status)
+    if app.run().is_error() {
+        std::process::exit(1);
+    }
 }
 
 /// Initialize the game world and print startup information.
@@ -109,5 +179,9 @@ fn setup_game_world(_commands: Commands) {
     println!();
     println!("💡 Send 'heartbeat' messages every 15s to maintain connection");
     println!("📤 Input format: {{\"Move\": {{\"direction\": [1.0, 0.0]}}}}");
+    println!("⚔️ Cast format: {{\"CastAtTarget\": {{\"ability\": \"strike\", \"target\": 10001}}}}");
+    println!("💬 Chat format: {{\"Chat\": {{\"channel\": \"Say\", \"text\": \"hello\"}}}}");
+    println!("🤝 Party format: {{\"Party\": {{\"Invite\": {{\"player\": 2}}}}}}, {{\"Party\": \"Accept\"}}");
+    println!("🎒 Inventory format: {{\"Inventory\": {{\"Use\": {{\"slot\": 0}}}}}}");
 }
 
@@ -0,0 +1,176 @@
+// Helpers for integration tests that drive real server processes over WebSocket.
+// Each test picks its own ports so tests can run in parallel.
+#![allow(dead_code)]
+
+use futures_util::{SinkExt, StreamExt};
+use hmac::Mac;
+use serde_json::Value;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+pub const TIMEOUT: Duration = Duration::from_secs(20);
+pub const ACCOUNT_SECRET: &str = "test-account-secret";
+
+// ============================================================================
+// SERVER PROCESS
+// ============================================================================
+
+pub struct Server {
+    child: Child,
+    output: Arc<Mutex<String>>,
+}
+
+impl Server {
+    // Starts the server binary from the crate root with only the given environment
+    pub fn start(envs: &[(&str, &str)]) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_mmo_game_server"))
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .env_clear()
+            .envs(envs.iter().copied())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("failed to start server");
+
+        let output = Arc::new(Mutex::new(String::new()));
+        let stdout = child.stdout.take().unwrap();
+        let sink = output.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                let mut output = sink.lock().unwrap();
+                output.push_str(&line);
+                output.push('\n');
+            }
+        });
+
+        Self { child, output }
+    }
+
+    pub fn output(&self) -> String {
+        self.output.lock().unwrap().clone()
+    }
+
+    pub fn wait_for_output(&self, needle: &str) -> bool {
+        let deadline = Instant::now() + TIMEOUT;
+        while Instant::now() < deadline {
+            if self.output().contains(needle) {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        false
+    }
+
+    // Asks the server to shut down cleanly, as Ctrl+C would
+    pub fn interrupt(&self) {
+        let _ = Command::new("kill").args(["-INT", &self.child.id().to_string()]).status();
+    }
+
+    // Waits for the process to exit and reports whether it succeeded
+    pub fn wait(mut self) -> bool {
+        let deadline = Instant::now() + TIMEOUT;
+        while Instant::now() < deadline {
+            if let Ok(Some(status)) = self.child.try_wait() {
+                // Give the reader thread a moment to drain the pipe
+                std::thread::sleep(Duration::from_millis(100));
+                return status.success();
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        false
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+pub fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("mmo_test_{}_{}", std::process::id(), name))
+}
+
+// ============================================================================
+// WEBSOCKET CLIENT
+// ============================================================================
+
+// Connection URL logging in as `account`, signed with ACCOUNT_SECRET
+pub fn account_url(port: u16, account: &str, handoff: Option<&str>) -> String {
+    let mut mac = hmac::Hmac::<sha2::Sha256>::new_from_slice(ACCOUNT_SECRET.as_bytes()).unwrap();
+    mac.update(account.as_bytes());
+    let sig: String = mac.finalize().into_bytes().iter().map(|byte| format!("{:02x}", byte)).collect();
+    let mut url = format!("ws://127.0.0.1:{}/?account={}&sig={}", port, account, sig);
+    if let Some(token) = handoff {
+        url.push_str(&format!("&handoff={}", token));
+    }
+    url
+}
+
+// The fields of `network_id`'s entity in a full sync or delta message, if it is in there
+pub fn entity_update<'a>(message: &'a Value, network_id: &Value) -> Option<&'a Value> {
+    message["u"].as_array()?.iter().find(|update| update["i"] == *network_id).map(|update| &update["c"])
+}
+
+pub struct Client {
+    ws: WebSocketStream<MaybeTlsStream<TcpStream>>,
+}
+
+impl Client {
+    // Retries until the server is listening
+    pub async fn connect(url: &str) -> Self {
+        let deadline = Instant::now() + TIMEOUT;
+        loop {
+            match tokio_tungstenite::connect_async(url).await {
+                Ok((ws, _)) => return Self { ws },
+                Err(e) if Instant::now() > deadline => panic!("could not connect to {}: {}", url, e),
+                Err(_) => tokio::time::sleep(Duration::from_millis(100)).await,
+            }
+        }
+    }
+
+    pub async fn send(&mut self, input: Value) {
+        self.ws.send(Message::text(input.to_string())).await.expect("send failed");
+    }
+
+    // Next JSON message, or None once the connection has closed
+    pub async fn next(&mut self) -> Option<Value> {
+        loop {
+            match tokio::time::timeout(TIMEOUT, self.ws.next()).await.ok()?? {
+                Ok(Message::Text(text)) => {
+                    if let Ok(value) = serde_json::from_str(&text) {
+                        return Some(value);
+                    }
+                }
+                Ok(Message::Close(_)) | Err(_) => return None,
+                Ok(_) => {}
+            }
+        }
+    }
+
+    // Skips messages until one of the given type arrives
+    pub async fn expect(&mut self, message_type: &str) -> Value {
+        while let Some(message) = self.next().await {
+            if message["t"] == message_type {
+                return message;
+            }
+        }
+        panic!("connection closed before a '{}' message", message_type);
+    }
+
+    pub async fn close(mut self) {
+        let _ = self.ws.close(None).await;
+    }
+
+    // Whether the server has closed the connection within the timeout
+    pub async fn closed(&mut self, timeout: Duration) -> bool {
+        tokio::time::timeout(timeout, async { while self.next().await.is_some() {} }).await.is_ok()
+    }
+}
@@ -0,0 +1,153 @@
+// Players who log off come back as they were and with what they had, whether the
+// profile store or only the world snapshot kept it: logging back in never refills an
+// inventory, heals or revives.
+
+mod common;
+
+use common::{account_url, entity_update, temp_path, Client, Server, ACCOUNT_SECRET};
+use serde_json::{json, Value};
+
+// Logs in and returns the full inventory the server sends on spawn
+async fn log_in(port: u16, account: &str) -> (Client, Value) {
+    let mut client = Client::connect(&account_url(port, account, None)).await;
+    loop {
+        let message = client.expect("inv").await;
+        if message["u"][0]["c"].get("cap").is_some() {
+            return (client, message["u"][0]["c"]["s"].clone());
+        }
+    }
+}
+
+// The next current health the server reports for `network_id`
+async fn next_health(client: &mut Client, network_id: &Value) -> f64 {
+    loop {
+        let message = client.next().await.expect("connection closed");
+        // Health is sent as [current, max]
+        if let Some(health) = entity_update(&message, network_id).and_then(|fields| fields["hp"][0].as_f64()) {
+            return health;
+        }
+    }
+}
+
+fn save_profile(dir: &std::path::Path, account: &str, x: f32, y: f32, health: Option<Value>) {
+    let mut profile = json!({
+        "position": { "x": x, "y": y },
+        "profile": { "max_speed": 100.0, "acceleration": 200.0, "deceleration": 300.0 },
+    });
+    if let Some(health) = health {
+        profile["health"] = health;
+    }
+    std::fs::write(dir.join(format!("{}.json", account)), profile.to_string()).unwrap();
+}
+
+// Drops two of the five starting bread and waits for the server to confirm it
+async fn drop_two_bread(client: &mut Client) {
+    client.send(json!({ "Inventory": { "Drop": { "slot": 1, "count": 2 } } })).await;
+    loop {
+        let message = client.expect("inv").await;
+        if message["u"][0]["c"]["s"] == json!([[1, "bread", 3]]) {
+            return;
+        }
+    }
+}
+
+#[tokio::test]
+async fn the_profile_store_keeps_inventories_across_logins() {
+    let player_dir = temp_path("persistence_players");
+    let server = Server::start(&[
+        ("WEBSOCKET_PORT", "5300"),
+        ("ACCOUNT_SECRET", ACCOUNT_SECRET),
+        ("PLAYER_DATA_DIR", player_dir.to_str().unwrap()),
+    ]);
+
+    let (mut client, slots) = log_in(5300, "hoarder").await;
+    assert_eq!(slots[0], json!([0, "health_potion", 3]));
+    assert_eq!(slots[1], json!([1, "bread", 5]));
+    drop_two_bread(&mut client).await;
+    client.close().await;
+    assert!(server.wait_for_output("💾 Saved profile for account hoarder"), "profile was not saved:\n{}", server.output());
+
+    let (_client, slots) = log_in(5300, "hoarder").await;
+    assert_eq!(slots[0], json!([0, "health_potion", 3]));
+    assert_eq!(slots[1], json!([1, "bread", 3]), "logging back in refilled the inventory");
+
+    // Guests have no record to keep, so they get nothing to drop and come back for
+    let mut guest = Client::connect("ws://127.0.0.1:5300").await;
+    let message = guest.expect("inv").await;
+    let slots = message["u"][0]["c"]["s"].as_array().unwrap();
+    assert!(slots.iter().all(|slot| slot[1].is_null()), "guest started with {:?}", slots);
+
+    let _ = std::fs::remove_dir_all(player_dir);
+}
+
+#[tokio::test]
+async fn logging_off_neither_heals_nor_revives() {
+    let player_dir = temp_path("persistence_health");
+    std::fs::create_dir_all(&player_dir).unwrap();
+    // An open corridor along the top of the default map
+    save_profile(&player_dir, "victim", 440.0, 75.0, None);
+    save_profile(&player_dir, "striker", 400.0, 75.0, None);
+    save_profile(&player_dir, "fallen", 300.0, 75.0, Some(json!({ "current": 0.0, "respawn_in_ticks": 20 })));
+    let server = Server::start(&[
+        ("WEBSOCKET_PORT", "5302"),
+        ("ACCOUNT_SECRET", ACCOUNT_SECRET),
+        ("PLAYER_DATA_DIR", player_dir.to_str().unwrap()),
+    ]);
+
+    let mut victim = Client::connect(&account_url(5302, "victim", None)).await;
+    let victim_id = victim.expect("w").await["u"][0]["c"]["network_id"].clone();
+    let mut striker = Client::connect(&account_url(5302, "striker", None)).await;
+    striker.expect("w").await;
+    striker.send(json!({ "CastAtTarget": { "ability": "strike", "target": victim_id } })).await;
+    let mut health = next_health(&mut victim, &victim_id).await;
+    while health >= 100.0 {
+        health = next_health(&mut victim, &victim_id).await;
+    }
+    victim.close().await;
+    assert!(server.wait_for_output("💾 Saved profile for account victim"), "profile was not saved:
+{}", server.output());
+
+    // Regeneration carries on until the save, so allow a little drift
+    let mut victim = Client::connect(&account_url(5302, "victim", None)).await;
+    let victim_id = victim.expect("w").await["u"][0]["c"]["network_id"].clone();
+    let returned = next_health(&mut victim, &victim_id).await;
+    assert!(returned < 100.0 && (returned - health).abs() <= 5.0, "health {} came back as {}", health, returned);
+
+    // A player saved dead sits out the rest of their respawn delay
+    let mut fallen = Client::connect(&account_url(5302, "fallen", None)).await;
+    let fallen_id = fallen.expect("w").await["u"][0]["c"]["network_id"].clone();
+    assert_eq!(next_health(&mut fallen, &fallen_id).await, 0.0, "the dead came back alive");
+    let mut health = 0.0;
+    while health == 0.0 {
+        health = next_health(&mut fallen, &fallen_id).await;
+    }
+    assert_eq!(health, 100.0);
+    assert!(server.output().contains("respawned"), "the dead never respawned:\n{}", server.output());
+
+    let _ = std::fs::remove_dir_all(player_dir);
+}
+
+#[tokio::test]
+async fn the_world_snapshot_keeps_inventories_across_logins_and_restarts() {
+    let snapshot = temp_path("persistence_snapshot.json");
+    let snapshot = snapshot.to_str().unwrap();
+    let envs = [("WEBSOCKET_PORT", "5301"), ("ACCOUNT_SECRET", ACCOUNT_SECRET), ("SNAPSHOT_PATH", snapshot)];
+
+    let server = Server::start(&envs);
+    let (mut client, _) = log_in(5301, "hoarder").await;
+    drop_two_bread(&mut client).await;
+    client.close().await;
+    assert!(server.wait_for_output("👋 Despawning player 1"), "player did not log off:\n{}", server.output());
+
+    let (client, slots) = log_in(5301, "hoarder").await;
+    assert_eq!(slots[1], json!([1, "bread", 3]), "logging back in refilled the inventory");
+    client.close().await;
+    server.interrupt();
+    assert!(server.wait(), "server did not shut down cleanly");
+
+    let server = Server::start(&envs);
+    let (_client, slots) = log_in(5301, "hoarder").await;
+    assert_eq!(slots[1], json!([1, "bread", 3]), "the snapshot lost the inventory:\n{}", server.output());
+
+    let _ = std::fs::remove_file(snapshot);
+}
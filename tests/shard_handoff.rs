@@ -0,0 +1,135 @@
+// Two shards on localhost: a player who walks across the border is handed to the
+// other process with their health, inventory and position, and the first one lets go.
+
+mod common;
+
+use common::{account_url, entity_update, temp_path, Client, Server, ACCOUNT_SECRET};
+use serde_json::json;
+use std::time::Duration;
+
+const SHARD_SECRET: &str = "test-shard-secret";
+
+fn save_profile(dir: &std::path::Path, account: &str, x: f32, y: f32) {
+    let profile = json!({
+        "position": { "x": x, "y": y },
+        "profile": { "max_speed": 100.0, "acceleration": 200.0, "deceleration": 300.0 },
+    });
+    std::fs::write(dir.join(format!("{}.json", account)), profile.to_string()).unwrap();
+}
+
+#[tokio::test]
+async fn walking_across_a_shard_border_hands_the_player_over() {
+    let shard_map = temp_path("shards.json");
+    std::fs::write(&shard_map, json!({
+        "shards": [
+            { "name": "west", "min_x": 0.0, "max_x": 500.0, "client_url": "ws://127.0.0.1:5200", "handoff_addr": "127.0.0.1:6200" },
+            { "name": "east", "min_x": 500.0, "max_x": 1000.0, "client_url": "ws://127.0.0.1:5201", "handoff_addr": "127.0.0.1:6201" },
+        ]
+    }).to_string()).unwrap();
+    let player_dir = temp_path("shard_players");
+    std::fs::create_dir_all(&player_dir).unwrap();
+    // An open corridor along the top of the default map, just west of the border
+    save_profile(&player_dir, "walker", 440.0, 75.0);
+    save_profile(&player_dir, "striker", 400.0, 75.0);
+
+    let shard_map = shard_map.to_str().unwrap();
+    let west = Server::start(&[
+        ("WEBSOCKET_PORT", "5200"),
+        ("SHARD_MAP", shard_map),
+        ("SHARD_NAME", "west"),
+        ("SHARD_SECRET", SHARD_SECRET),
+        ("ACCOUNT_SECRET", ACCOUNT_SECRET),
+        ("PLAYER_DATA_DIR", player_dir.to_str().unwrap()),
+    ]);
+    let east = Server::start(&[
+        ("WEBSOCKET_PORT", "5201"),
+        ("SHARD_MAP", shard_map),
+        ("SHARD_NAME", "east"),
+        ("SHARD_SECRET", SHARD_SECRET),
+        ("ACCOUNT_SECRET", ACCOUNT_SECRET),
+    ]);
+    assert!(west.wait_for_output("🔀 Listening for shard handoffs"), "west shard did not start:\n{}", west.output());
+    assert!(east.wait_for_output("🔀 Listening for shard handoffs"), "east shard did not start:\n{}", east.output());
+
+    let mut walker = Client::connect(&account_url(5200, "walker", None)).await;
+    let welcome = walker.expect("w").await;
+    let walker_id = welcome["u"][0]["c"]["network_id"].clone();
+    let mut striker = Client::connect(&account_url(5200, "striker", None)).await;
+    striker.expect("w").await;
+
+    // Leave neither health nor inventory at their starting values
+    striker.send(json!({ "CastAtTarget": { "ability": "strike", "target": walker_id } })).await;
+    walker.send(json!({ "Inventory": { "Drop": { "slot": 1, "count": 2 } } })).await;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    walker.send(json!({ "Move": { "direction": [1.0, 0.0] } })).await;
+
+    // Follow the walker's own state on the west shard until it redirects them
+    let mut west_health = None;
+    let mut west_slots = None;
+    let redirect = loop {
+        let message = walker.next().await.expect("west shard closed the connection without a redirect");
+        match message["t"].as_str() {
+            Some("redirect") => break message["u"][0]["c"].clone(),
+            Some("inv") => {
+                if let Some(slots) = message["u"][0]["c"].get("s") {
+                    west_slots = Some(slots.clone());
+                }
+            }
+            _ => {
+                // Health is sent as [current, max]
+                if let Some(health) = entity_update(&message, &walker_id).and_then(|fields| fields["hp"][0].as_f64()) {
+                    west_health = Some(health);
+                }
+            }
+        }
+    };
+    assert!(walker.closed(Duration::from_secs(5)).await, "west shard kept the redirected connection open");
+    let west_health = west_health.expect("west shard never reported the walker's health");
+    assert!(west_health < 100.0, "the strike did not land (health {})", west_health);
+    assert_eq!(west_slots, Some(json!([[1, "bread", 3]])), "the drop did not register");
+
+    // The source lets go of the player as soon as the handoff is accepted
+    assert!(west.wait_for_output("👋 Despawning player 1"), "west shard still holds the walker:\n{}", west.output());
+
+    let url = redirect["url"].as_str().unwrap();
+    assert_eq!(url, "ws://127.0.0.1:5201");
+    let mut arrived = Client::connect(&account_url(5201, "walker", redirect["tok"].as_str())).await;
+    let welcome = arrived.expect("w").await;
+    let arrived_id = welcome["u"][0]["c"]["network_id"].clone();
+
+    let mut east_health = None;
+    let mut east_position = None;
+    let mut east_slots = None;
+    while east_health.is_none() || east_position.is_none() || east_slots.is_none() {
+        let message = arrived.next().await.expect("east shard closed the connection");
+        if message["t"] == "inv" {
+            east_slots = message["u"][0]["c"]["s"].as_array().cloned();
+            continue;
+        }
+        if let Some(fields) = entity_update(&message, &arrived_id) {
+            east_health = fields["hp"][0].as_f64().or(east_health);
+            east_position = fields["p"].as_array().cloned().or(east_position);
+        }
+    }
+
+    // Health regenerates on both sides in the meantime, so allow a little drift
+    let east_health = east_health.unwrap();
+    assert!((east_health - west_health).abs() <= 5.0, "health {} became {}", west_health, east_health);
+    let position = east_position.unwrap();
+    let (x, y) = (position[0].as_f64().unwrap(), position[1].as_f64().unwrap());
+    assert!((500.0..560.0).contains(&x) && (y - 75.0).abs() < 5.0, "arrived at ({}, {})", x, y);
+    let slots = east_slots.unwrap();
+    assert_eq!(slots[0], json!([0, "health_potion", 3]));
+    assert_eq!(slots[1], json!([1, "bread", 3]));
+    assert!(east.output().contains("🔀 Player 1 arrived by handoff"), "east shard output:\n{}", east.output());
+
+    // Shard traffic signed with anything but the shared secret is refused
+    let mut forged = std::net::TcpStream::connect("127.0.0.1:6201").unwrap();
+    let message = json!({ "Accepted": { "token": "0000000000000000" } }).to_string();
+    let line = json!({ "message": message, "sig": "00".repeat(32) }).to_string() + "\n";
+    std::io::Write::write_all(&mut forged, line.as_bytes()).unwrap();
+    assert!(east.wait_for_output("❌ Dropping shard connection"), "east shard accepted a forged message");
+
+    let _ = std::fs::remove_file(shard_map);
+    let _ = std::fs::remove_dir_all(player_dir);
+}